@@ -0,0 +1,457 @@
+use crate::config::MonitorConfig;
+use crate::{service_excluded, service_matches, Severity, SystemInfo};
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Alert {
+    pub source: String,
+    pub severity: Severity,
+    pub message: String,
+}
+
+pub fn evaluate_alerts(info: &SystemInfo, config: &MonitorConfig) -> Vec<Alert> {
+    let mut alerts = Vec::new();
+    if !config.alerts.enabled {
+        return alerts;
+    }
+
+    // Kritik servisler
+    for service in info.services.iter().filter(|s| s.critical && !s.active) {
+        alerts.push(Alert {
+            source: format!("service.{}", service.name),
+            severity: Severity::Critical,
+            message: format!("Kritik servis çalışmıyor: {}", service.name),
+        });
+    }
+
+    // Başarısız ve sürekli yeniden başlayan servisler
+    for service in info.services.iter() {
+        if service.active_state.as_deref() == Some("failed") && !service.critical {
+            alerts.push(Alert {
+                source: format!("service.{}", service.name),
+                severity: Severity::Warning,
+                message: format!("Servis başarısız durumda: {}", service.name),
+            });
+        }
+        if service.flapping {
+            alerts.push(Alert {
+                source: format!("service.{}", service.name),
+                severity: Severity::Warning,
+                message: format!(
+                    "Servis sürekli yeniden başlıyor: {} (pencere içinde {} kez)",
+                    service.name,
+                    service.restarts_in_window.unwrap_or(0)
+                ),
+            });
+        }
+    }
+
+    // Glob içermeyen kritik servisler sistemde hiç bulunamadıysa da bildir; hariç tutulanlar toplanmadığı için atlanır
+    for pattern in config.services.critical_services.iter() {
+        let is_glob = pattern.contains(['*', '?']);
+        let found = info
+            .services
+            .iter()
+            .any(|s| service_matches(pattern, &s.name, &config.services.aliases));
+        let excluded = std::iter::once(pattern)
+            .chain(config.services.aliases.get(pattern.trim_end_matches(".service")).into_iter().flatten())
+            .any(|name| service_excluded(name, &config.services));
+        if !is_glob && !found && !excluded {
+            alerts.push(Alert {
+                source: format!("service.{}", pattern),
+                severity: Severity::Critical,
+                message: format!("Kritik servis bulunamadı: {}", pattern),
+            });
+        }
+    }
+
+    // Süreç izleme kuralları
+    for finding in info.process_findings.iter() {
+        alerts.push(Alert {
+            source: finding.check.clone(),
+            severity: finding.severity,
+            message: finding.message.clone(),
+        });
+    }
+
+    // SMART: arızalı veya yıpranmış diskler
+    for disk in info.hardware.disk_health.iter() {
+        let source = format!("disk.{}", disk.device.trim_start_matches("/dev/"));
+        if disk.passed == Some(false) {
+            alerts.push(Alert {
+                source: source.clone(),
+                severity: Severity::Critical,
+                message: format!("SMART sağlık testi başarısız: {}", disk.device),
+            });
+        }
+        let bad_sectors = disk.reallocated_sectors.unwrap_or(0) + disk.pending_sectors.unwrap_or(0);
+        if bad_sectors > 0 || disk.media_errors.unwrap_or(0) > 0 {
+            alerts.push(Alert {
+                source: source.clone(),
+                severity: Severity::Warning,
+                message: format!(
+                    "Diskte hatalı sektör/ortam hatası: {} (yeniden atanan {}, bekleyen {}, ortam hatası {})",
+                    disk.device,
+                    disk.reallocated_sectors.unwrap_or(0),
+                    disk.pending_sectors.unwrap_or(0),
+                    disk.media_errors.unwrap_or(0)
+                ),
+            });
+        }
+        if let Some(used) = disk.percentage_used.filter(|u| *u >= config.hardware.smart.percentage_used_warning) {
+            alerts.push(Alert {
+                source,
+                severity: Severity::Warning,
+                message: format!("Disk ömrünün %{}'i tüketildi: {}", used, disk.device),
+            });
+        }
+    }
+
+    // RAID, LVM ince havuzları ve ZFS
+    if let Some(storage) = &info.storage {
+        for array in storage.md_arrays.iter().filter(|a| a.degraded) {
+            alerts.push(Alert {
+                source: format!("storage.{}", array.name),
+                severity: Severity::Critical,
+                message: format!(
+                    "RAID dizisi bozuk: {} (arızalı: {})",
+                    array.name,
+                    if array.failed_devices.is_empty() { "-".to_string() } else { array.failed_devices.join(", ") }
+                ),
+            });
+        }
+        for pool in storage.thin_pools.iter() {
+            let usage = pool.data_percent.into_iter().chain(pool.metadata_percent).fold(0.0f32, f32::max);
+            if usage >= config.storage.thin_pool_warning_percent {
+                alerts.push(Alert {
+                    source: format!("storage.{}/{}", pool.vg_name, pool.name),
+                    severity: Severity::Warning,
+                    message: format!("LVM ince havuzu dolmak üzere: {}/{} (%{:.1})", pool.vg_name, pool.name, usage),
+                });
+            }
+        }
+        for pool in storage.zfs_pools.iter() {
+            if pool.health != "ONLINE" {
+                alerts.push(Alert {
+                    source: format!("storage.{}", pool.name),
+                    severity: if pool.health == "DEGRADED" { Severity::Warning } else { Severity::Critical },
+                    message: format!("ZFS havuzu sağlıksız: {} ({})", pool.name, pool.health),
+                });
+            }
+            if let Some(cap) = pool.capacity_percent.filter(|c| *c >= config.storage.zfs_capacity_warning_percent) {
+                alerts.push(Alert {
+                    source: format!("storage.{}", pool.name),
+                    severity: Severity::Warning,
+                    message: format!("ZFS havuzu dolmak üzere: {} (%{})", pool.name, cap),
+                });
+            }
+        }
+    }
+
+    // Saat senkronizasyonu; zaman damgaları ancak saat doğruysa güvenilirdir
+    if info.time_sync.synchronized == Some(false) {
+        alerts.push(Alert {
+            source: "time.sync".to_string(),
+            severity: Severity::Warning,
+            message: format!(
+                "Saat senkronize değil ({})",
+                info.time_sync.daemon.as_deref().unwrap_or("NTP istemcisi bulunamadı")
+            ),
+        });
+    }
+    if let Some(offset) = info.time_sync.offset_ms.filter(|o| o.abs() > config.time.max_offset_ms) {
+        alerts.push(Alert {
+            source: "time.offset".to_string(),
+            severity: Severity::Warning,
+            message: format!("Saat farkı yüksek: {:.1} ms", offset),
+        });
+    }
+
+    // TLS sertifikaları
+    for cert in info.certificates.iter() {
+        let source = format!("cert.{}", cert.source);
+        if let Some(error) = &cert.error {
+            alerts.push(Alert {
+                source,
+                severity: Severity::Warning,
+                message: format!("Sertifika okunamadı: {} ({})", cert.source, error),
+            });
+            continue;
+        }
+        let Some(days) = cert.days_until_expiry else {
+            continue;
+        };
+        let severity = if cert.expired || days <= config.certificates.critical_days {
+            Severity::Critical
+        } else if days <= config.certificates.warning_days {
+            Severity::Warning
+        } else {
+            continue;
+        };
+        let message = if cert.expired {
+            format!("Sertifikanın süresi dolmuş: {} ({})", cert.source, cert.subject.as_deref().unwrap_or("-"))
+        } else {
+            format!(
+                "Sertifikanın süresi {} gün içinde doluyor: {} ({})",
+                days,
+                cert.source,
+                cert.subject.as_deref().unwrap_or("-")
+            )
+        };
+        alerts.push(Alert { source, severity, message });
+    }
+
+    // Günlük izlemeleri
+    for result in info.log_watches.iter() {
+        let source = format!("log.{}", result.name);
+        if let Some(error) = &result.error {
+            alerts.push(Alert {
+                source,
+                severity: Severity::Warning,
+                message: format!("Günlük okunamadı: {} ({})", result.source, error),
+            });
+            continue;
+        }
+        let threshold = config
+            .logs
+            .watches
+            .iter()
+            .find(|watch| watch.name == result.name)
+            .and_then(|watch| watch.max_matches);
+        if let Some(threshold) = threshold.filter(|threshold| result.matches > *threshold) {
+            alerts.push(Alert {
+                source,
+                severity: Severity::Warning,
+                message: format!(
+                    "{} günlüğünde son döngüde {} eşleşen satır var (sınır: {})",
+                    result.source, result.matches, threshold
+                ),
+            });
+        }
+    }
+
+    // Bellek yetersizliğinden öldürülen süreçler
+    for kill in info.oom_kills.iter() {
+        alerts.push(Alert {
+            source: format!("oom.{}", kill.process),
+            severity: Severity::Warning,
+            message: format!("OOM killer süreci sonlandırdı: {} (PID {})", kill.process, kill.pid),
+        });
+    }
+
+    alerts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::certs::CertificateInfo;
+    use crate::config::LogWatch;
+    use crate::logwatch::LogWatchResult;
+    use crate::storage::{MdArray, StorageHealth, ZfsPool};
+    use crate::ServiceInfo;
+
+    fn certificate(source: &str, days: i64) -> CertificateInfo {
+        CertificateInfo {
+            source: source.to_string(),
+            subject: Some("CN=example.com".to_string()),
+            sans: Vec::new(),
+            issuer: None,
+            not_before: None,
+            not_after: None,
+            days_until_expiry: Some(days),
+            expired: days < 0,
+            error: None,
+        }
+    }
+
+    fn log_result(name: &str, matches: u64) -> LogWatchResult {
+        LogWatchResult {
+            name: name.to_string(),
+            source: format!("/var/log/{}.log", name),
+            lines_read: matches,
+            matches,
+            pattern_matches: Default::default(),
+            samples: Vec::new(),
+            rotated: false,
+            truncated: false,
+            error: None,
+        }
+    }
+
+    fn md_array(name: &str, degraded: bool) -> MdArray {
+        MdArray {
+            name: name.to_string(),
+            level: Some("raid1".to_string()),
+            state: "active".to_string(),
+            devices: vec!["sda1".to_string(), "sdb1".to_string()],
+            failed_devices: if degraded { vec!["sdb1".to_string()] } else { Vec::new() },
+            total_devices: Some(2),
+            active_devices: Some(if degraded { 1 } else { 2 }),
+            degraded,
+            sync_action: None,
+            sync_progress_percent: None,
+        }
+    }
+
+    fn zfs_pool(name: &str, health: &str, capacity: u32) -> ZfsPool {
+        ZfsPool {
+            name: name.to_string(),
+            health: health.to_string(),
+            size_bytes: 0,
+            allocated_bytes: 0,
+            free_bytes: 0,
+            capacity_percent: Some(capacity),
+            fragmentation_percent: None,
+        }
+    }
+
+    fn config() -> MonitorConfig {
+        let mut config = MonitorConfig::default();
+        config.services.critical_services = Vec::new();
+        config
+    }
+
+    fn severity_of(alerts: &[Alert], source: &str) -> Option<Severity> {
+        alerts.iter().find(|a| a.source == source).map(|a| a.severity)
+    }
+
+    #[test]
+    fn grades_certificates_by_expiry() {
+        let mut config = config();
+        config.certificates.warning_days = 30;
+        config.certificates.critical_days = 7;
+        let mut unreadable = certificate("/etc/letsencrypt/live", 0);
+        unreadable.days_until_expiry = None;
+        unreadable.error = Some("Permission denied".to_string());
+        let info = SystemInfo {
+            certificates: vec![
+                certificate("far", 31),
+                certificate("warning", 30),
+                certificate("near-critical", 8),
+                certificate("critical", 7),
+                certificate("expired", -2),
+                unreadable,
+            ],
+            ..Default::default()
+        };
+
+        let alerts = evaluate_alerts(&info, &config);
+        assert_eq!(severity_of(&alerts, "cert.far"), None);
+        assert_eq!(severity_of(&alerts, "cert.warning"), Some(Severity::Warning));
+        assert_eq!(severity_of(&alerts, "cert.near-critical"), Some(Severity::Warning));
+        assert_eq!(severity_of(&alerts, "cert.critical"), Some(Severity::Critical));
+        assert_eq!(severity_of(&alerts, "cert.expired"), Some(Severity::Critical));
+        assert_eq!(severity_of(&alerts, "cert./etc/letsencrypt/live"), Some(Severity::Warning));
+    }
+
+    #[test]
+    fn warns_when_log_matches_exceed_threshold() {
+        let mut config = config();
+        config.logs.watches = ["at-limit", "over-limit", "unlimited"]
+            .iter()
+            .map(|name| LogWatch {
+                name: name.to_string(),
+                max_matches: (*name != "unlimited").then_some(50),
+                ..Default::default()
+            })
+            .collect();
+        let mut failed = log_result("missing", 0);
+        failed.error = Some("Permission denied".to_string());
+        let info = SystemInfo {
+            log_watches: vec![
+                log_result("at-limit", 50),
+                log_result("over-limit", 51),
+                log_result("unlimited", 10_000),
+                failed,
+            ],
+            ..Default::default()
+        };
+
+        let alerts = evaluate_alerts(&info, &config);
+        assert_eq!(severity_of(&alerts, "log.at-limit"), None);
+        assert_eq!(severity_of(&alerts, "log.over-limit"), Some(Severity::Warning));
+        assert_eq!(severity_of(&alerts, "log.unlimited"), None);
+        assert_eq!(severity_of(&alerts, "log.missing"), Some(Severity::Warning));
+    }
+
+    #[test]
+    fn reports_degraded_arrays_and_full_pools() {
+        let mut config = config();
+        config.storage.zfs_capacity_warning_percent = 80;
+        let info = SystemInfo {
+            storage: Some(StorageHealth {
+                md_arrays: vec![md_array("md0", false), md_array("md1", true)],
+                volume_groups: Vec::new(),
+                thin_pools: Vec::new(),
+                zfs_pools: vec![
+                    zfs_pool("tank", "ONLINE", 79),
+                    zfs_pool("full", "ONLINE", 80),
+                    zfs_pool("backup", "DEGRADED", 10),
+                    zfs_pool("broken", "FAULTED", 10),
+                ],
+            }),
+            ..Default::default()
+        };
+
+        let alerts = evaluate_alerts(&info, &config);
+        assert_eq!(severity_of(&alerts, "storage.md0"), None);
+        assert_eq!(severity_of(&alerts, "storage.md1"), Some(Severity::Critical));
+        assert!(alerts.iter().any(|a| a.source == "storage.md1" && a.message.contains("sdb1")));
+        assert_eq!(severity_of(&alerts, "storage.tank"), None);
+        assert_eq!(severity_of(&alerts, "storage.full"), Some(Severity::Warning));
+        assert_eq!(severity_of(&alerts, "storage.backup"), Some(Severity::Warning));
+        assert_eq!(severity_of(&alerts, "storage.broken"), Some(Severity::Critical));
+    }
+
+    #[test]
+    fn reports_critical_flapping_and_missing_services() {
+        let mut config = config();
+        config.services.critical_services = vec!["nginx".to_string(), "postgresql".to_string(), "redis".to_string()];
+        config.services.excluded_services = vec!["redis".to_string()];
+        let info = SystemInfo {
+            services: vec![
+                ServiceInfo {
+                    name: "nginx.service".to_string(),
+                    critical: true,
+                    active: false,
+                    active_state: Some("failed".to_string()),
+                    ..Default::default()
+                },
+                ServiceInfo {
+                    name: "worker.service".to_string(),
+                    active: true,
+                    flapping: true,
+                    restarts_in_window: Some(6),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        let alerts = evaluate_alerts(&info, &config);
+        // Kritik servis başarısızsa ayrıca "başarısız" uyarısı üretilmez
+        let nginx: Vec<&Alert> = alerts.iter().filter(|a| a.source == "service.nginx.service").collect();
+        assert_eq!(nginx.len(), 1);
+        assert_eq!(nginx[0].severity, Severity::Critical);
+        assert_eq!(severity_of(&alerts, "service.worker.service"), Some(Severity::Warning));
+        assert_eq!(severity_of(&alerts, "service.postgresql"), Some(Severity::Critical));
+        assert_eq!(severity_of(&alerts, "service.redis"), None);
+    }
+
+    #[test]
+    fn checks_clock_offset_and_respects_disable() {
+        let mut config = config();
+        config.time.max_offset_ms = 500.0;
+        let mut info = SystemInfo::default();
+        info.time_sync.synchronized = Some(true);
+        info.time_sync.offset_ms = Some(-500.0);
+        assert_eq!(severity_of(&evaluate_alerts(&info, &config), "time.offset"), None);
+
+        info.time_sync.offset_ms = Some(-500.1);
+        assert_eq!(severity_of(&evaluate_alerts(&info, &config), "time.offset"), Some(Severity::Warning));
+
+        config.alerts.enabled = false;
+        assert!(evaluate_alerts(&info, &config).is_empty());
+    }
+}
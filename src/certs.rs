@@ -0,0 +1,334 @@
+use crate::config::CertificateConfig;
+use anyhow::{anyhow, Context, Result};
+use openssl::asn1::{Asn1Time, Asn1TimeRef};
+use openssl::hash::MessageDigest;
+use openssl::ssl::{SslConnector, SslMethod, SslVerifyMode};
+use openssl::x509::{X509NameRef, X509};
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::fs;
+use std::io::ErrorKind;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, TcpStream, ToSocketAddrs};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const MAX_DEPTH: usize = 4;
+
+// Sertifikalar nadiren değişir; tarama aralık boyunca tekrar kullanılır
+static LAST_RESULT: Mutex<Option<(Instant, Vec<CertificateInfo>)>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CertificateInfo {
+    // Dosya yolu veya host:port
+    pub source: String,
+    pub subject: Option<String>,
+    pub sans: Vec<String>,
+    pub issuer: Option<String>,
+    pub not_before: Option<String>,
+    pub not_after: Option<String>,
+    pub days_until_expiry: Option<i64>,
+    pub expired: bool,
+    pub error: Option<String>,
+}
+
+pub fn get_certificates(config: &CertificateConfig) -> Vec<CertificateInfo> {
+    if !config.enabled {
+        return Vec::new();
+    }
+
+    let mut last = LAST_RESULT.lock().unwrap_or_else(|e| e.into_inner());
+    let interval = Duration::from_secs(config.interval_minutes * 60);
+    if let Some((at, result)) = last.as_ref() {
+        if at.elapsed() < interval {
+            return result.clone();
+        }
+    }
+
+    let mut certificates = Vec::new();
+    // cert.pem ve fullchain.pem aynı sertifikayı içerir, parmak iziyle tekilleştirilir
+    let mut seen = BTreeSet::new();
+    for path in &config.paths {
+        scan_path(Path::new(path), 0, &mut seen, &mut certificates);
+    }
+    for endpoint in &config.endpoints {
+        certificates.push(check_endpoint(endpoint).unwrap_or_else(|e| unreadable(endpoint, e.to_string())));
+    }
+
+    *last = Some((Instant::now(), certificates.clone()));
+    certificates
+}
+
+fn scan_path(path: &Path, depth: usize, seen: &mut BTreeSet<Vec<u8>>, certificates: &mut Vec<CertificateInfo>) {
+    // letsencrypt/live altındaki dosyalar archive dizinine sembolik bağlantıdır, metadata bağlantıyı izler.
+    // Olmayan yollar (örn. certbot kurulu değil) atlanır; okunamayan yollar uyarı üretsin diye raporlanır
+    let metadata = match fs::metadata(path) {
+        Ok(metadata) => metadata,
+        Err(e) if e.kind() == ErrorKind::NotFound => return,
+        Err(e) => {
+            certificates.push(unreadable(&path.to_string_lossy(), e.to_string()));
+            return;
+        }
+    };
+
+    if metadata.is_dir() {
+        if depth >= MAX_DEPTH {
+            return;
+        }
+        let entries = match fs::read_dir(path) {
+            Ok(entries) => entries,
+            Err(e) => {
+                certificates.push(unreadable(&path.to_string_lossy(), e.to_string()));
+                return;
+            }
+        };
+        let mut paths: Vec<_> = entries.filter_map(|e| e.ok()).map(|e| e.path()).collect();
+        paths.sort();
+        for child in paths {
+            scan_path(&child, depth + 1, seen, certificates);
+        }
+        return;
+    }
+
+    let is_certificate_file = path
+        .extension()
+        .map(|ext| matches!(ext.to_string_lossy().as_ref(), "pem" | "crt" | "cer"))
+        .unwrap_or(false);
+    if !is_certificate_file {
+        return;
+    }
+
+    // Anahtar dosyaları ve bozuk içerikler sessizce atlanır; zincirde yalnızca ilk (yaprak) sertifika raporlanır
+    let Some(cert) = fs::read(path)
+        .ok()
+        .and_then(|pem| X509::stack_from_pem(&pem).ok())
+        .and_then(|stack| stack.into_iter().next())
+    else {
+        return;
+    };
+    let fingerprint = cert.digest(MessageDigest::sha256()).map(|d| d.to_vec()).unwrap_or_default();
+    if seen.insert(fingerprint) {
+        certificates.push(describe(&path.to_string_lossy(), &cert));
+    }
+}
+
+fn unreadable(source: &str, error: String) -> CertificateInfo {
+    CertificateInfo {
+        source: source.to_string(),
+        subject: None,
+        sans: Vec::new(),
+        issuer: None,
+        not_before: None,
+        not_after: None,
+        days_until_expiry: None,
+        expired: false,
+        error: Some(error),
+    }
+}
+
+fn check_endpoint(endpoint: &str) -> Result<CertificateInfo> {
+    let (host, _) = endpoint.rsplit_once(':').context("Uç nokta host:port biçiminde olmalı")?;
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    let addr = endpoint
+        .to_socket_addrs()?
+        .next()
+        .with_context(|| format!("{} çözümlenemedi", endpoint))?;
+
+    let tcp = TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT)?;
+    tcp.set_read_timeout(Some(CONNECT_TIMEOUT))?;
+    tcp.set_write_timeout(Some(CONNECT_TIMEOUT))?;
+
+    // Amaç doğrulama değil sertifikayı okumak; süresi dolmuş sertifikalar da raporlanmalı
+    let mut builder = SslConnector::builder(SslMethod::tls_client())?;
+    builder.set_verify(SslVerifyMode::NONE);
+    let mut connect = builder.build().configure()?;
+    connect.set_verify_hostname(false);
+    connect.set_use_server_name_indication(host.parse::<IpAddr>().is_err());
+    let stream = connect.connect(host, tcp).map_err(|e| anyhow!("TLS el sıkışması başarısız: {}", e))?;
+
+    let cert = stream
+        .ssl()
+        .peer_certificate()
+        .context("Sunucu sertifika göndermedi")?;
+    Ok(describe(endpoint, &cert))
+}
+
+fn describe(source: &str, cert: &X509) -> CertificateInfo {
+    let sans = cert
+        .subject_alt_names()
+        .map(|names| {
+            names
+                .iter()
+                .filter_map(|name| {
+                    if let Some(dns) = name.dnsname() {
+                        Some(format!("DNS:{}", dns))
+                    } else if let Some(ip) = name.ipaddress() {
+                        ip_to_string(ip).map(|ip| format!("IP:{}", ip))
+                    } else {
+                        name.email().map(|email| format!("email:{}", email))
+                    }
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // TimeDiff gün ve saniye olarak döner; süresi dolmuş sertifikalarda ikisi de negatiftir
+    let days_until_expiry = Asn1Time::days_from_now(0)
+        .and_then(|now| now.diff(cert.not_after()))
+        .ok()
+        .map(|diff| diff.days as i64);
+    let expired = Asn1Time::days_from_now(0)
+        .map(|now| cert.not_after() < now)
+        .unwrap_or(false);
+
+    CertificateInfo {
+        source: source.to_string(),
+        subject: format_name(cert.subject_name()),
+        sans,
+        issuer: format_name(cert.issuer_name()),
+        not_before: asn1_to_rfc3339(cert.not_before()),
+        not_after: asn1_to_rfc3339(cert.not_after()),
+        days_until_expiry,
+        expired,
+        error: None,
+    }
+}
+
+// "CN=example.com, O=Example"
+fn format_name(name: &X509NameRef) -> Option<String> {
+    let parts: Vec<String> = name
+        .entries()
+        .filter_map(|entry| {
+            let key = entry.object().nid().short_name().ok()?;
+            let value = entry.data().to_string().ok()?;
+            Some(format!("{}={}", key, value))
+        })
+        .collect();
+    (!parts.is_empty()).then(|| parts.join(", "))
+}
+
+// OpenSSL biçimi: "Oct 19 12:00:00 2026 GMT"
+fn asn1_to_rfc3339(time: &Asn1TimeRef) -> Option<String> {
+    chrono::NaiveDateTime::parse_from_str(&time.to_string(), "%b %e %H:%M:%S %Y GMT")
+        .ok()
+        .map(|naive| naive.and_utc().to_rfc3339())
+}
+
+fn ip_to_string(bytes: &[u8]) -> Option<String> {
+    match bytes.len() {
+        4 => Some(Ipv4Addr::new(bytes[0], bytes[1], bytes[2], bytes[3]).to_string()),
+        16 => <[u8; 16]>::try_from(bytes).ok().map(|b| Ipv6Addr::from(b).to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openssl::asn1::Asn1Integer;
+    use openssl::bn::BigNum;
+    use openssl::ec::{EcGroup, EcKey};
+    use openssl::nid::Nid;
+    use openssl::pkey::{PKey, Private};
+    use openssl::ssl::SslAcceptor;
+    use openssl::x509::extension::SubjectAlternativeName;
+    use openssl::x509::{X509Builder, X509NameBuilder};
+    use std::net::TcpListener;
+    use std::thread;
+
+    // localhost için kendinden imzalı, verilen gün sonra süresi dolan sertifika
+    fn self_signed(days: u32) -> (X509, PKey<Private>) {
+        let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+        let key = PKey::from_ec_key(EcKey::generate(&group).unwrap()).unwrap();
+
+        let mut name = X509NameBuilder::new().unwrap();
+        name.append_entry_by_text("CN", "localhost").unwrap();
+        name.append_entry_by_text("O", "StaffMon Test").unwrap();
+        let name = name.build();
+
+        let mut builder = X509Builder::new().unwrap();
+        builder.set_version(2).unwrap();
+        let serial: Asn1Integer = BigNum::from_u32(1).unwrap().to_asn1_integer().unwrap();
+        builder.set_serial_number(&serial).unwrap();
+        builder.set_subject_name(&name).unwrap();
+        builder.set_issuer_name(&name).unwrap();
+        builder.set_pubkey(&key).unwrap();
+        builder.set_not_before(&Asn1Time::days_from_now(0).unwrap()).unwrap();
+        builder.set_not_after(&Asn1Time::days_from_now(days).unwrap()).unwrap();
+        let san = SubjectAlternativeName::new()
+            .dns("localhost")
+            .ip("127.0.0.1")
+            .build(&builder.x509v3_context(None, None))
+            .unwrap();
+        builder.append_extension(san).unwrap();
+        builder.sign(&key, MessageDigest::sha256()).unwrap();
+        (builder.build(), key)
+    }
+
+    #[test]
+    fn reads_certificate_from_local_tls_server() {
+        let (cert, key) = self_signed(10);
+        let mut acceptor = SslAcceptor::mozilla_intermediate(SslMethod::tls()).unwrap();
+        acceptor.set_private_key(&key).unwrap();
+        acceptor.set_certificate(&cert).unwrap();
+        let acceptor = acceptor.build();
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            // İstemci sertifikayı okuyup bağlantıyı kapatır; sonucu önemsiz
+            let _ = acceptor.accept(stream);
+        });
+
+        let info = check_endpoint(&format!("127.0.0.1:{}", port)).unwrap();
+        server.join().unwrap();
+
+        assert_eq!(info.subject.as_deref(), Some("CN=localhost, O=StaffMon Test"));
+        assert_eq!(info.issuer, info.subject);
+        assert_eq!(info.sans, vec!["DNS:localhost", "IP:127.0.0.1"]);
+        assert!((9..=10).contains(&info.days_until_expiry.unwrap()));
+        assert!(!info.expired);
+    }
+
+    #[test]
+    fn scans_certificate_files_once_per_fingerprint() {
+        let (cert, key) = self_signed(40);
+        let dir = std::env::temp_dir().join(format!("staffmon-certs-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let pem = cert.to_pem().unwrap();
+        fs::write(dir.join("cert.pem"), &pem).unwrap();
+        fs::write(dir.join("fullchain.pem"), &pem).unwrap();
+        fs::write(dir.join("privkey.pem"), key.private_key_to_pem_pkcs8().unwrap()).unwrap();
+
+        let mut seen = BTreeSet::new();
+        let mut certificates = Vec::new();
+        scan_path(&dir, 0, &mut seen, &mut certificates);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(certificates.len(), 1);
+        assert!(certificates[0].source.ends_with("cert.pem"));
+        assert!((39..=40).contains(&certificates[0].days_until_expiry.unwrap()));
+    }
+
+    #[test]
+    fn reports_unreadable_paths_and_skips_missing_ones() {
+        let dir = std::env::temp_dir().join(format!("staffmon-certs-unreadable-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("cert.pem"), "").unwrap();
+
+        // Testler root olarak çalışabildiğinden izin hatası yerine "dizin değil" hatası kullanılır
+        let broken = dir.join("cert.pem").join("live");
+        let mut seen = BTreeSet::new();
+        let mut certificates = Vec::new();
+        scan_path(&dir.join("missing"), 0, &mut seen, &mut certificates);
+        scan_path(&broken, 0, &mut seen, &mut certificates);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(certificates.len(), 1);
+        assert_eq!(certificates[0].source, broken.to_string_lossy());
+        assert!(certificates[0].error.is_some());
+        assert_eq!(certificates[0].days_until_expiry, None);
+    }
+}
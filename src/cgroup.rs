@@ -0,0 +1,206 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+
+#[derive(Debug, Default, Serialize)]
+pub struct ServiceResources {
+    pub cpu_usage_usec: Option<u64>,
+    pub memory_current_bytes: Option<u64>,
+    pub memory_peak_bytes: Option<u64>,
+    pub tasks: Option<u64>,
+    pub io_read_bytes: Option<u64>,
+    pub io_write_bytes: Option<u64>,
+}
+
+// control_group: systemd'nin ControlGroup özelliği, ör. "/system.slice/nginx.service"
+pub fn get_service_resources(control_group: &str) -> Option<ServiceResources> {
+    cgroup_resources(Path::new(CGROUP_ROOT), control_group)
+}
+
+fn cgroup_resources(root: &Path, control_group: &str) -> Option<ServiceResources> {
+    let path = control_group.trim_start_matches('/');
+    if path.is_empty() {
+        return None;
+    }
+    if is_unified(root) {
+        get_resources_v2(&root.join(path))
+    } else {
+        // v1'de aynı göreli yol her denetleyici hiyerarşisinin altında bulunur
+        get_resources_v1(|name| root.join(name).join(path))
+    }
+}
+
+// Sürecin bulunduğu cgroup'un kaynak kullanımı (konteynerler gibi systemd dışı gruplar için)
+pub fn get_process_resources(pid: u32) -> Option<ServiceResources> {
+    let paths = process_cgroups(pid)?;
+    if is_unified(Path::new(CGROUP_ROOT)) {
+        let path = paths.get("")?;
+        get_resources_v2(&Path::new(CGROUP_ROOT).join(path.trim_start_matches('/')))
+    } else {
+        get_resources_v1(|name| {
+            // "cpu,cpuacct" gibi birleşik bağlama noktaları için her denetleyici ayrı aranır
+            let path = name
+                .split(',')
+                .find_map(|controller| paths.get(controller))
+                .map(|p| p.trim_start_matches('/'))
+                .unwrap_or("");
+            Path::new(CGROUP_ROOT).join(name).join(path)
+        })
+    }
+}
+
+// /proc/<pid>/cgroup: "0::/system.slice/docker-<id>.scope" (v2) veya "4:memory:/docker/<id>" (v1)
+pub fn process_cgroups(pid: u32) -> Option<HashMap<String, String>> {
+    let content = fs::read_to_string(format!("/proc/{}/cgroup", pid)).ok()?;
+    let mut paths = HashMap::new();
+    for line in content.lines() {
+        let mut fields = line.splitn(3, ':');
+        let (Some(_), Some(controllers), Some(path)) = (fields.next(), fields.next(), fields.next()) else {
+            continue;
+        };
+        if controllers.is_empty() {
+            paths.insert(String::new(), path.to_string());
+        }
+        for controller in controllers.split(',').filter(|c| !c.is_empty()) {
+            paths.insert(controller.to_string(), path.to_string());
+        }
+    }
+    Some(paths)
+}
+
+// cgroup v2 birleşik hiyerarşi kök dizinde cgroup.controllers dosyası içerir
+fn is_unified(root: &Path) -> bool {
+    root.join("cgroup.controllers").exists()
+}
+
+fn get_resources_v2(dir: &Path) -> Option<ServiceResources> {
+    if !dir.is_dir() {
+        return None;
+    }
+
+    let cpu_usage_usec = fs::read_to_string(dir.join("cpu.stat"))
+        .ok()
+        .and_then(|stat| keyed_value(&stat, "usage_usec"));
+
+    let (io_read_bytes, io_write_bytes) = fs::read_to_string(dir.join("io.stat"))
+        .map(|stat| {
+            // 8:0 rbytes=1234 wbytes=5678 rios=1 wios=2 ... (her aygıt için bir satır)
+            let mut read = 0;
+            let mut write = 0;
+            for field in stat.split_whitespace() {
+                if let Some(v) = field.strip_prefix("rbytes=") {
+                    read += v.parse::<u64>().unwrap_or(0);
+                } else if let Some(v) = field.strip_prefix("wbytes=") {
+                    write += v.parse::<u64>().unwrap_or(0);
+                }
+            }
+            (Some(read), Some(write))
+        })
+        .unwrap_or((None, None));
+
+    Some(ServiceResources {
+        cpu_usage_usec,
+        memory_current_bytes: read_u64(&dir.join("memory.current")),
+        memory_peak_bytes: read_u64(&dir.join("memory.peak")),
+        tasks: read_u64(&dir.join("pids.current")),
+        io_read_bytes,
+        io_write_bytes,
+    })
+}
+
+fn get_resources_v1(controller: impl Fn(&str) -> PathBuf) -> Option<ServiceResources> {
+    // Bazı dağıtımlar cpuacct denetleyicisini ayrı bağlar
+    let cpu_dir = ["cpu,cpuacct", "cpuacct"]
+        .iter()
+        .map(|name| controller(name))
+        .find(|dir| dir.is_dir())
+        .unwrap_or_else(|| controller("cpuacct"));
+    let memory_dir = controller("memory");
+    if !cpu_dir.is_dir() && !memory_dir.is_dir() {
+        return None;
+    }
+
+    let (io_read_bytes, io_write_bytes) = fs::read_to_string(controller("blkio").join("blkio.throttle.io_service_bytes"))
+        .map(|stat| {
+            // 8:0 Read 1234 / 8:0 Write 5678 / Total 6912
+            let mut read = 0;
+            let mut write = 0;
+            for line in stat.lines() {
+                let fields: Vec<&str> = line.split_whitespace().collect();
+                if fields.len() == 3 {
+                    match fields[1] {
+                        "Read" => read += fields[2].parse::<u64>().unwrap_or(0),
+                        "Write" => write += fields[2].parse::<u64>().unwrap_or(0),
+                        _ => {}
+                    }
+                }
+            }
+            (Some(read), Some(write))
+        })
+        .unwrap_or((None, None));
+
+    Some(ServiceResources {
+        // cpuacct.usage nanosaniye cinsindendir
+        cpu_usage_usec: read_u64(&cpu_dir.join("cpuacct.usage")).map(|ns| ns / 1000),
+        memory_current_bytes: read_u64(&memory_dir.join("memory.usage_in_bytes")),
+        memory_peak_bytes: read_u64(&memory_dir.join("memory.max_usage_in_bytes")),
+        tasks: read_u64(&controller("pids").join("pids.current")),
+        io_read_bytes,
+        io_write_bytes,
+    })
+}
+
+fn read_u64(path: &Path) -> Option<u64> {
+    fs::read_to_string(path).ok().and_then(|v| v.trim().parse().ok())
+}
+
+fn keyed_value(content: &str, key: &str) -> Option<u64> {
+    content
+        .lines()
+        .filter_map(|line| line.split_once(' '))
+        .find(|(k, _)| *k == key)
+        .and_then(|(_, v)| v.trim().parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/cgroup").join(name)
+    }
+
+    #[test]
+    fn reads_unified_hierarchy() {
+        let resources = cgroup_resources(&fixture("v2"), "/system.slice/nginx.service").unwrap();
+        assert_eq!(resources.cpu_usage_usec, Some(8_431_207));
+        assert_eq!(resources.memory_current_bytes, Some(12_582_912));
+        assert_eq!(resources.memory_peak_bytes, Some(20_971_520));
+        assert_eq!(resources.tasks, Some(5));
+        // io.stat iki aygıt için toplanır
+        assert_eq!(resources.io_read_bytes, Some(4_198_400));
+        assert_eq!(resources.io_write_bytes, Some(1_052_672));
+
+        // Özel slice altındaki birimler de ControlGroup yolundan bulunur
+        let resources = cgroup_resources(&fixture("v2"), "/app.slice/worker.service").unwrap();
+        assert_eq!(resources.tasks, Some(2));
+        assert_eq!(resources.memory_peak_bytes, None);
+
+        assert!(cgroup_resources(&fixture("v2"), "/system.slice/missing.service").is_none());
+        assert!(cgroup_resources(&fixture("v2"), "").is_none());
+    }
+
+    #[test]
+    fn reads_legacy_hierarchy() {
+        let resources = cgroup_resources(&fixture("v1"), "/system.slice/nginx.service").unwrap();
+        assert_eq!(resources.cpu_usage_usec, Some(8_431_207));
+        assert_eq!(resources.memory_current_bytes, Some(12_582_912));
+        assert_eq!(resources.memory_peak_bytes, Some(20_971_520));
+        assert_eq!(resources.tasks, Some(5));
+        assert_eq!(resources.io_read_bytes, Some(4_198_400));
+        assert_eq!(resources.io_write_bytes, Some(1_052_672));
+    }
+}
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::Path;
-use anyhow::Result;
+use anyhow::{bail, Result};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ApiConfig {
@@ -43,4 +44,480 @@ impl ApiConfig {
 
         Ok(config)
     }
-} 
\ No newline at end of file
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MonitorConfig {
+    pub security: SecurityConfig,
+    pub inventory: InventoryConfig,
+    pub services: ServicesConfig,
+    pub processes: ProcessConfig,
+    pub containers: ContainerConfig,
+    pub hardware: HardwareConfig,
+    pub storage: StorageConfig,
+    pub time: TimeConfig,
+    pub certificates: CertificateConfig,
+    pub reboot: RebootConfig,
+    pub logs: LogsConfig,
+    pub alerts: AlertConfig,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SecurityConfig {
+    pub ssh: SshConfig,
+    pub kernel: KernelConfig,
+    pub packages: PackageConfig,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SshConfig {
+    pub enabled: bool,
+    pub config_path: String,
+    pub use_sshd_t: bool,
+    // sshd -T ve yapılandırma dosyaları bu aralıkla yeniden okunur
+    pub interval_minutes: u64,
+    pub allowed_root_login: Vec<String>,
+    pub allow_password_authentication: bool,
+    pub require_allow_users: bool,
+    pub allowed_ports: Vec<u16>,
+    pub weak_ciphers: Vec<String>,
+    pub weak_macs: Vec<String>,
+    pub weak_kex: Vec<String>,
+}
+
+impl Default for SshConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            config_path: "/etc/ssh/sshd_config".to_string(),
+            use_sshd_t: true,
+            interval_minutes: 10,
+            allowed_root_login: vec!["no".to_string(), "prohibit-password".to_string(), "without-password".to_string()],
+            allow_password_authentication: false,
+            require_allow_users: false,
+            allowed_ports: Vec::new(),
+            weak_ciphers: [
+                "3des-cbc", "aes128-cbc", "aes192-cbc", "aes256-cbc", "blowfish-cbc",
+                "cast128-cbc", "arcfour", "arcfour128", "arcfour256", "rijndael-cbc@lysator.liu.se",
+            ].iter().map(|s| s.to_string()).collect(),
+            weak_macs: [
+                "hmac-md5", "hmac-md5-96", "hmac-sha1", "hmac-sha1-96", "umac-64@openssh.com",
+                "hmac-md5-etm@openssh.com", "hmac-md5-96-etm@openssh.com", "hmac-sha1-etm@openssh.com",
+                "hmac-sha1-96-etm@openssh.com", "umac-64-etm@openssh.com",
+            ].iter().map(|s| s.to_string()).collect(),
+            weak_kex: [
+                "diffie-hellman-group1-sha1", "diffie-hellman-group14-sha1",
+                "diffie-hellman-group-exchange-sha1",
+            ].iter().map(|s| s.to_string()).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KernelConfig {
+    pub enabled: bool,
+    // Beklenen değerler; "1|2" gibi alternatifler '|' ile ayrılır
+    pub expected_sysctls: BTreeMap<String, String>,
+    pub require_mac: bool,
+    pub require_secure_boot: bool,
+    pub denied_modules: Vec<String>,
+}
+
+impl Default for KernelConfig {
+    fn default() -> Self {
+        let expected_sysctls = [
+            ("net.ipv4.ip_forward", "0"),
+            ("net.ipv4.conf.all.rp_filter", "1|2"),
+            ("net.ipv4.conf.all.accept_redirects", "0"),
+            ("net.ipv4.conf.all.send_redirects", "0"),
+            ("net.ipv4.conf.all.accept_source_route", "0"),
+            ("net.ipv4.tcp_syncookies", "1"),
+            ("kernel.kptr_restrict", "1|2"),
+            ("kernel.randomize_va_space", "2"),
+            ("kernel.dmesg_restrict", "1"),
+            ("kernel.yama.ptrace_scope", "1|2|3"),
+            ("kernel.unprivileged_bpf_disabled", "1|2"),
+            ("fs.protected_symlinks", "1"),
+            ("fs.protected_hardlinks", "1"),
+            ("fs.suid_dumpable", "0"),
+        ]
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+
+        Self {
+            enabled: true,
+            expected_sysctls,
+            require_mac: true,
+            require_secure_boot: false,
+            denied_modules: [
+                "cramfs", "freevxfs", "jffs2", "hfs", "hfsplus", "udf", "dccp", "sctp", "rds", "tipc",
+            ].iter().map(|s| s.to_string()).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PackageConfig {
+    // Güncelleme listesi indeks veya kurulu paketler değişmese de bu aralıkla yeniden okunur
+    pub updates_interval_hours: u64,
+}
+
+impl Default for PackageConfig {
+    fn default() -> Self {
+        Self {
+            updates_interval_hours: 24,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InventoryConfig {
+    pub enabled: bool,
+    pub interval_minutes: u64,
+    // Göreli yol durum dizinine göre çözülür
+    pub snapshot_path: String,
+}
+
+impl Default for InventoryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_minutes: 60,
+            snapshot_path: "package_inventory.json".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ServicesConfig {
+    // Glob desenleri; boş liste tüm servisleri izler
+    pub monitored_services: Vec<String>,
+    pub excluded_services: Vec<String>,
+    // Çalışmadığında uyarı üreten servisler; monitored_services dışında kalsalar da toplanır
+    pub critical_services: Vec<String>,
+    // Dağıtımlara göre farklı adlarla gelen servisler
+    pub aliases: BTreeMap<String, Vec<String>>,
+    // Pencere içinde bu kadar yeniden başlatılan servis flapping sayılır
+    pub flap_window_minutes: u64,
+    pub flap_threshold: u32,
+    // Servis başına açık sürüm komutu, örn. nginx = ["nginx", "-v"]
+    pub version_commands: BTreeMap<String, Vec<String>>,
+}
+
+impl Default for ServicesConfig {
+    fn default() -> Self {
+        let aliases = [
+            ("mysql", vec!["mariadb", "mysqld"]),
+            ("redis", vec!["redis-server"]),
+            ("nginx", vec!["nginx-main"]),
+        ]
+        .into_iter()
+        .map(|(name, alternatives)| (name.to_string(), alternatives.into_iter().map(String::from).collect()))
+        .collect();
+
+        Self {
+            monitored_services: Vec::new(),
+            excluded_services: Vec::new(),
+            critical_services: Vec::new(),
+            aliases,
+            flap_window_minutes: 10,
+            flap_threshold: 3,
+            version_commands: BTreeMap::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProcessSort {
+    Cpu,
+    Memory,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProcessConfig {
+    // 0 tüm süreçleri gönderir
+    pub top_n: usize,
+    pub sort_by: ProcessSort,
+    pub include_command_line: bool,
+    // Komut satırında maskelenecek regex desenleri; ilk grup korunur
+    pub redact_patterns: Vec<String>,
+    pub tree: bool,
+    // Bu süreden uzun zombi veya D durumunda kalan süreçler raporlanır
+    pub stuck_threshold_seconds: u64,
+    // Her döngüde tüm süreçlere karşı değerlendirilen kurallar
+    pub required: Vec<ProcessRule>,
+    pub forbidden: Vec<ProcessRule>,
+}
+
+// Belirtilen tüm ölçütler eşleşmelidir; hiç ölçüt içermeyen kural hiçbir süreçle eşleşmez
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProcessRule {
+    pub name: String,
+    // Süreç adı için glob deseni
+    pub process_name: Option<String>,
+    // Çalıştırılabilir dosya yolu için glob deseni
+    pub exe: Option<String>,
+    // Komut satırı için regex
+    pub cmdline: Option<String>,
+    // Yalnızca required kuralları için; min verilmezse 1
+    pub min_instances: Option<usize>,
+    pub max_instances: Option<usize>,
+}
+
+impl Default for ProcessConfig {
+    fn default() -> Self {
+        Self {
+            top_n: 25,
+            sort_by: ProcessSort::Cpu,
+            include_command_line: true,
+            redact_patterns: vec![
+                r"(?i)(--?(?:password|passwd|pass|token|secret|api[-_]?key)[= ])\S+".to_string(),
+                r"(?i)(://[^:/\s]+:)[^@\s]+".to_string(),
+            ],
+            tree: false,
+            stuck_threshold_seconds: 60,
+            required: Vec::new(),
+            forbidden: vec![
+                ProcessRule {
+                    name: "cryptominer".to_string(),
+                    cmdline: Some(r"(?i)(xmrig|minerd|cpuminer|stratum\+(tcp|ssl)://)".to_string()),
+                    ..Default::default()
+                },
+                ProcessRule {
+                    name: "netcat-listener".to_string(),
+                    cmdline: Some(r"^(\S*/)?(nc|ncat|netcat)(\.\S+)?\s(.*\s)?-\w*l".to_string()),
+                    ..Default::default()
+                },
+            ],
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ContainerConfig {
+    pub enabled: bool,
+    // Docker/Podman API soketleri; mevcut olanların hepsi sorgulanır
+    pub sockets: Vec<String>,
+}
+
+impl Default for ContainerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            sockets: vec!["/var/run/docker.sock".to_string(), "/run/podman/podman.sock".to_string()],
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HardwareConfig {
+    pub smart: SmartConfig,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SmartConfig {
+    pub enabled: bool,
+    pub interval_minutes: u64,
+    // SSD/NVMe ömür tüketimi bu yüzdeyi aşınca uyarı üretilir
+    pub percentage_used_warning: u64,
+}
+
+impl Default for SmartConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_minutes: 30,
+            percentage_used_warning: 90,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StorageConfig {
+    pub enabled: bool,
+    // lvs/vgs/zpool bu aralıkla yeniden çalıştırılır; /proc/mdstat her döngüde okunur
+    pub interval_minutes: u64,
+    pub thin_pool_warning_percent: f32,
+    pub zfs_capacity_warning_percent: u32,
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_minutes: 5,
+            thin_pool_warning_percent: 80.0,
+            zfs_capacity_warning_percent: 80,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TimeConfig {
+    // Saat farkı bu değeri aşarsa uyarı üretilir
+    pub max_offset_ms: f64,
+}
+
+impl Default for TimeConfig {
+    fn default() -> Self {
+        Self { max_offset_ms: 500.0 }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RebootConfig {
+    // needs-restarting ve /proc/*/maps taraması bu aralıkla tekrarlanır
+    pub interval_minutes: u64,
+}
+
+impl Default for RebootConfig {
+    fn default() -> Self {
+        Self { interval_minutes: 10 }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CertificateConfig {
+    pub enabled: bool,
+    // Dosya veya dizinler; dizinlerde .pem/.crt/.cer dosyaları taranır
+    pub paths: Vec<String>,
+    // host:port biçiminde TLS dinleyicileri
+    pub endpoints: Vec<String>,
+    pub interval_minutes: u64,
+    pub warning_days: i64,
+    pub critical_days: i64,
+}
+
+impl Default for CertificateConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            paths: vec!["/etc/letsencrypt/live".to_string()],
+            endpoints: Vec::new(),
+            interval_minutes: 60,
+            warning_days: 30,
+            critical_days: 7,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LogsConfig {
+    pub enabled: bool,
+    // İzleme başına yüke eklenen en fazla örnek satır
+    pub max_samples: usize,
+    // Bir döngüde dosya başına okunan en fazla bayt; kalan kısım sonraki döngüde okunur
+    pub max_read_bytes: u64,
+    pub watches: Vec<LogWatch>,
+}
+
+impl LogsConfig {
+    // İzleme durumu ve alarm eşikleri ada göre tutulur; aynı ad iki izlemenin durumunu karıştırır
+    pub fn validate(&self) -> Result<()> {
+        let mut names = std::collections::BTreeSet::new();
+        for watch in &self.watches {
+            if !names.insert(watch.name.as_str()) {
+                bail!("logs.watches içinde yinelenen izleme adı: {}", watch.name);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for LogsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_samples: 5,
+            max_read_bytes: 4 * 1024 * 1024,
+            watches: Vec::new(),
+        }
+    }
+}
+
+// path verilirse dosya, verilmezse systemd journal izlenir
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LogWatch {
+    pub name: String,
+    pub path: Option<String>,
+    // Yalnızca journal için; boşsa tüm birimler
+    pub units: Vec<String>,
+    // Yalnızca journal için; journalctl -p değeri (ör. "err", "0..3")
+    pub priority: Option<String>,
+    // Satır bu regex'lerden herhangi biriyle eşleşirse sayılır; boşsa her satır sayılır
+    pub patterns: Vec<String>,
+    // Bir döngüdeki eşleşme sayısı bunu aşarsa uyarı üretilir
+    pub max_matches: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AlertConfig {
+    pub enabled: bool,
+}
+
+impl Default for AlertConfig {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+impl MonitorConfig {
+    pub fn load() -> Result<Self> {
+        // İzleme ayarları da aynı config.toml dosyasından okunur
+        let config_path = Path::new("config.toml");
+        let config = if config_path.exists() {
+            let settings = config::Config::builder()
+                .add_source(config::File::from(config_path))
+                .build()?;
+
+            settings.try_deserialize::<Self>()?
+        } else {
+            Self::default()
+        };
+
+        config.logs.validate()?;
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_duplicate_log_watch_names() {
+        let watch = |name: &str| LogWatch {
+            name: name.to_string(),
+            ..Default::default()
+        };
+        let mut logs = LogsConfig {
+            watches: vec![watch("nginx"), watch("app")],
+            ..Default::default()
+        };
+        assert!(logs.validate().is_ok());
+
+        logs.watches.push(watch("nginx"));
+        assert!(logs.validate().is_err());
+    }
+}
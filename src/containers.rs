@@ -0,0 +1,291 @@
+use crate::cgroup::{self, ServiceResources};
+use crate::config::ContainerConfig;
+use anyhow::{bail, Context, Result};
+use log::debug;
+use regex::Regex;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::os::unix::net::UnixStream;
+use std::path::Path;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+const SOCKET_TIMEOUT: Duration = Duration::from_secs(5);
+
+static CONTAINER_ID: OnceLock<Regex> = OnceLock::new();
+
+#[derive(Debug, Serialize)]
+pub struct ContainerInfo {
+    pub id: String,
+    pub name: String,
+    pub image: String,
+    pub runtime: String,
+    pub state: String,
+    pub status: String,
+    pub health: Option<String>,
+    pub restart_count: Option<u64>,
+    pub pid: Option<u32>,
+    pub resources: Option<ServiceResources>,
+    pub network_rx_bytes: Option<u64>,
+    pub network_tx_bytes: Option<u64>,
+}
+
+// Docker ve Podman uyumlu API alanları
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ContainerSummary {
+    id: String,
+    #[serde(default)]
+    names: Vec<String>,
+    #[serde(default)]
+    image: String,
+    #[serde(default)]
+    state: String,
+    #[serde(default)]
+    status: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ContainerInspect {
+    restart_count: Option<u64>,
+    state: Option<InspectState>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct InspectState {
+    pid: Option<u32>,
+    health: Option<InspectHealth>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct InspectHealth {
+    status: String,
+}
+
+pub fn get_containers(config: &ContainerConfig) -> Vec<ContainerInfo> {
+    if !config.enabled {
+        return Vec::new();
+    }
+
+    let mut containers = Vec::new();
+    for socket in config.sockets.iter().filter(|s| Path::new(s).exists()) {
+        let runtime = if socket.contains("podman") { "podman" } else { "docker" };
+        match list_containers(Path::new(socket), runtime) {
+            Ok(list) => containers.extend(list),
+            Err(e) => debug!("Konteyner listesi alınamadı ({}): {}", socket, e),
+        }
+    }
+    containers
+}
+
+fn list_containers(socket: &Path, runtime: &str) -> Result<Vec<ContainerInfo>> {
+    let summaries: Vec<ContainerSummary> = get_json(socket, "/containers/json?all=true")?;
+
+    let mut containers = Vec::new();
+    for summary in summaries {
+        // Inspect başarısız olursa liste bilgisiyle devam edilir
+        let inspect: Option<ContainerInspect> = get_json(socket, &format!("/containers/{}/json", summary.id)).ok();
+        let state = inspect.as_ref().and_then(|i| i.state.as_ref());
+        let pid = state.and_then(|s| s.pid).filter(|pid| *pid != 0);
+        let (network_rx_bytes, network_tx_bytes) = pid.and_then(network_usage).unzip();
+
+        containers.push(ContainerInfo {
+            name: summary
+                .names
+                .first()
+                .map(|n| n.trim_start_matches('/').to_string())
+                .unwrap_or_default(),
+            image: summary.image,
+            runtime: runtime.to_string(),
+            state: summary.state,
+            status: summary.status,
+            health: state.and_then(|s| s.health.as_ref()).map(|h| h.status.clone()),
+            restart_count: inspect.as_ref().and_then(|i| i.restart_count),
+            pid,
+            resources: pid.and_then(cgroup::get_process_resources),
+            network_rx_bytes,
+            network_tx_bytes,
+            id: summary.id,
+        });
+    }
+    Ok(containers)
+}
+
+// Konteynerin ağ ad alanındaki arayüz sayaçları, lo hariç
+fn network_usage(pid: u32) -> Option<(u64, u64)> {
+    let content = fs::read_to_string(format!("/proc/{}/net/dev", pid)).ok()?;
+    let mut rx = 0;
+    let mut tx = 0;
+    // İlk iki satır başlık; "eth0: rx_bytes rx_packets ... (8 alan) tx_bytes ..."
+    for line in content.lines().skip(2) {
+        let Some((iface, counters)) = line.split_once(':') else {
+            continue;
+        };
+        if iface.trim() == "lo" {
+            continue;
+        }
+        let fields: Vec<u64> = counters.split_whitespace().filter_map(|f| f.parse().ok()).collect();
+        if fields.len() >= 9 {
+            rx += fields[0];
+            tx += fields[8];
+        }
+    }
+    Some((rx, tx))
+}
+
+// Sürecin cgroup yolundan konteyner kimliğini çıkarır (docker-<id>.scope, libpod-<id>.scope, /docker/<id>)
+pub fn container_id_for_pid(pid: u32) -> Option<String> {
+    let regex = CONTAINER_ID.get_or_init(|| Regex::new(r"(?:docker|libpod)[-/]([0-9a-f]{64})").unwrap());
+    cgroup::process_cgroups(pid)?
+        .values()
+        .find_map(|path| regex.captures(path).map(|caps| caps[1].to_string()))
+}
+
+fn get_json<T: DeserializeOwned>(socket: &Path, path: &str) -> Result<T> {
+    let body = http_get(socket, path)?;
+    serde_json::from_slice(&body).with_context(|| format!("{} yanıtı çözümlenemedi", path))
+}
+
+// Unix soket üzerinden basit bir HTTP/1.1 GET isteği
+fn http_get(socket: &Path, path: &str) -> Result<Vec<u8>> {
+    let mut stream = UnixStream::connect(socket)?;
+    stream.set_read_timeout(Some(SOCKET_TIMEOUT))?;
+    stream.set_write_timeout(Some(SOCKET_TIMEOUT))?;
+    write!(stream, "GET {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n", path)?;
+
+    let mut reader = BufReader::new(stream);
+    let mut status_line = String::new();
+    reader.read_line(&mut status_line)?;
+    let status: u16 = status_line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|code| code.parse().ok())
+        .context("Geçersiz HTTP yanıtı")?;
+
+    let mut chunked = false;
+    let mut content_length = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            break;
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            let value = value.trim();
+            if name.eq_ignore_ascii_case("transfer-encoding") && value.eq_ignore_ascii_case("chunked") {
+                chunked = true;
+            } else if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.parse::<usize>().ok();
+            }
+        }
+    }
+
+    let mut body = Vec::new();
+    if chunked {
+        loop {
+            let mut size_line = String::new();
+            reader.read_line(&mut size_line)?;
+            let size = usize::from_str_radix(size_line.trim().split(';').next().unwrap_or(""), 16)
+                .context("Geçersiz chunk boyutu")?;
+            if size == 0 {
+                break;
+            }
+            let start = body.len();
+            body.resize(start + size, 0);
+            reader.read_exact(&mut body[start..])?;
+            // Her chunk'ın sonundaki CRLF
+            let mut crlf = [0u8; 2];
+            reader.read_exact(&mut crlf)?;
+        }
+    } else if let Some(length) = content_length {
+        body.resize(length, 0);
+        reader.read_exact(&mut body)?;
+    } else {
+        reader.read_to_end(&mut body)?;
+    }
+
+    if !(200..300).contains(&status) {
+        bail!("{} isteği başarısız: HTTP {}", path, status);
+    }
+    Ok(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::net::UnixListener;
+    use std::thread;
+
+    // Docker API'yi taklit eden soket; istenen yola göre hazır yanıt döner
+    fn serve(socket: &Path, responses: Vec<(&'static str, &'static str)>) -> thread::JoinHandle<()> {
+        let listener = UnixListener::bind(socket).unwrap();
+        thread::spawn(move || {
+            for _ in 0..responses.len() {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let path = request_line.split_whitespace().nth(1).unwrap_or("").to_string();
+
+                let body = responses
+                    .iter()
+                    .find(|(p, _)| *p == path)
+                    .map(|(_, body)| *body)
+                    .unwrap_or("{}");
+                // Docker gibi chunked yanıt gönder, gövdeyi iki parçaya böl
+                let (first, second) = body.split_at(body.len() / 2);
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nTransfer-Encoding: chunked\r\n\r\n{:x}\r\n{}\r\n{:x}\r\n{}\r\n0\r\n\r\n",
+                    first.len(),
+                    first,
+                    second.len(),
+                    second
+                )
+                .unwrap();
+            }
+        })
+    }
+
+    #[test]
+    fn lists_containers_from_socket() {
+        let socket = std::env::temp_dir().join(format!("staffmon-docker-{}.sock", std::process::id()));
+        let _ = fs::remove_file(&socket);
+        let server = serve(
+            &socket,
+            vec![
+                (
+                    "/containers/json?all=true",
+                    r#"[{"Id":"abc123","Names":["/web"],"Image":"nginx:1.25","State":"running","Status":"Up 2 hours (healthy)"},
+                        {"Id":"def456","Names":["/job"],"Image":"busybox","State":"exited","Status":"Exited (0) 3 minutes ago"}]"#,
+                ),
+                (
+                    "/containers/abc123/json",
+                    r#"{"RestartCount":2,"State":{"Status":"running","Pid":0,"Health":{"Status":"healthy"}}}"#,
+                ),
+                ("/containers/def456/json", r#"{"RestartCount":0,"State":{"Status":"exited","Pid":0}}"#),
+            ],
+        );
+
+        let containers = list_containers(&socket, "docker").unwrap();
+        server.join().unwrap();
+        let _ = fs::remove_file(&socket);
+
+        assert_eq!(containers.len(), 2);
+        assert_eq!(containers[0].name, "web");
+        assert_eq!(containers[0].image, "nginx:1.25");
+        assert_eq!(containers[0].health.as_deref(), Some("healthy"));
+        assert_eq!(containers[0].restart_count, Some(2));
+        assert_eq!(containers[0].pid, None);
+        assert_eq!(containers[1].state, "exited");
+        assert_eq!(containers[1].health, None);
+    }
+}
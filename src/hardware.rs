@@ -0,0 +1,303 @@
+use crate::config::SmartConfig;
+use crate::virt;
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+const SMBIOS_ENTRIES: &str = "/sys/firmware/dmi/entries";
+
+// DMI bilgileri ve bellek modülleri çalışma sırasında değişmez, bir kez okunur
+static SYSTEM_DMI: OnceLock<SystemDmi> = OnceLock::new();
+static MEMORY_MODULES: OnceLock<Vec<MemoryModule>> = OnceLock::new();
+
+// Diskler sıcak takılıp değiştirilebilir; envanter SMART aralığında yenilenir ki yeni diskler de denetlensin
+static BLOCK_DEVICES: Mutex<Option<(Instant, Vec<BlockDevice>)>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SystemDmi {
+    pub manufacturer: Option<String>,
+    pub product: Option<String>,
+    // Seri numarası ve UUID dosyaları yalnızca root tarafından okunabilir
+    pub serial: Option<String>,
+    pub uuid: Option<String>,
+    pub bios_vendor: Option<String>,
+    pub bios_version: Option<String>,
+    pub bios_date: Option<String>,
+    pub board_vendor: Option<String>,
+    pub board_name: Option<String>,
+    pub board_serial: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MemoryModule {
+    pub locator: Option<String>,
+    pub bank: Option<String>,
+    pub size_mb: u64,
+    pub memory_type: Option<String>,
+    pub speed_mts: Option<u16>,
+    pub manufacturer: Option<String>,
+    pub serial: Option<String>,
+    pub part_number: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BlockDevice {
+    pub name: String,
+    pub model: Option<String>,
+    pub vendor: Option<String>,
+    pub serial: Option<String>,
+    pub size_bytes: u64,
+    pub rotational: Option<bool>,
+    pub removable: bool,
+    pub transport: Option<String>,
+}
+
+pub fn get_system_dmi() -> &'static SystemDmi {
+    SYSTEM_DMI.get_or_init(read_system_dmi)
+}
+
+fn read_system_dmi() -> SystemDmi {
+    let mut dmi = SystemDmi {
+        manufacturer: virt::dmi_field("sys_vendor"),
+        product: virt::dmi_field("product_name"),
+        serial: virt::dmi_field("product_serial"),
+        uuid: virt::dmi_field("product_uuid"),
+        bios_vendor: virt::dmi_field("bios_vendor"),
+        bios_version: virt::dmi_field("bios_version"),
+        bios_date: virt::dmi_field("bios_date"),
+        board_vendor: virt::dmi_field("board_vendor"),
+        board_name: virt::dmi_field("board_name"),
+        board_serial: virt::dmi_field("board_serial"),
+    };
+
+    // sysfs DMI yoksa (eski çekirdekler) dmidecode'un tekil anahtarları denenir; konteynerde anlamsız
+    if dmi.manufacturer.is_none() && !virt::in_container() {
+        dmi.manufacturer = dmidecode_string("system-manufacturer");
+        dmi.product = dmidecode_string("system-product-name");
+        dmi.serial = dmidecode_string("system-serial-number");
+        dmi.uuid = dmidecode_string("system-uuid");
+        dmi.bios_vendor = dmidecode_string("bios-vendor");
+        dmi.bios_version = dmidecode_string("bios-version");
+        dmi.bios_date = dmidecode_string("bios-release-date");
+        dmi.board_vendor = dmidecode_string("baseboard-manufacturer");
+        dmi.board_name = dmidecode_string("baseboard-product-name");
+        dmi.board_serial = dmidecode_string("baseboard-serial-number");
+    }
+    dmi
+}
+
+fn dmidecode_string(keyword: &str) -> Option<String> {
+    let output = Command::new("dmidecode").args(["-s", keyword]).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(String::from)
+}
+
+// SMBIOS tip 17 (Memory Device) kayıtları; ham dosyalar root gerektirir
+pub fn get_memory_modules() -> &'static [MemoryModule] {
+    MEMORY_MODULES.get_or_init(read_memory_modules)
+}
+
+fn read_memory_modules() -> Vec<MemoryModule> {
+    let Ok(entries) = fs::read_dir(SMBIOS_ENTRIES) else {
+        return Vec::new();
+    };
+
+    let mut dirs: Vec<_> = entries
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_name().to_string_lossy().starts_with("17-"))
+        .map(|e| e.path())
+        .collect();
+    dirs.sort();
+
+    dirs.iter()
+        .filter_map(|dir| fs::read(dir.join("raw")).ok())
+        .filter_map(|raw| parse_memory_device(&raw))
+        .collect()
+}
+
+fn parse_memory_device(raw: &[u8]) -> Option<MemoryModule> {
+    let length = *raw.get(1)? as usize;
+    if raw.len() < length || length < 0x15 {
+        return None;
+    }
+    let word = |offset: usize| -> Option<u16> {
+        (offset + 2 <= length).then(|| u16::from_le_bytes([raw[offset], raw[offset + 1]]))
+    };
+    let dword = |offset: usize| -> Option<u32> {
+        (offset + 4 <= length)
+            .then(|| raw[offset..offset + 4].try_into().ok())
+            .flatten()
+            .map(u32::from_le_bytes)
+    };
+    let byte = |offset: usize| -> Option<u8> { (offset < length).then(|| raw[offset]) };
+
+    // Biçimlendirilmiş alanın ardından NUL ile biten dizeler gelir, indeks 1'den başlar
+    let strings: Vec<String> = raw[length..]
+        .split(|b| *b == 0)
+        .take_while(|s| !s.is_empty())
+        .map(|s| String::from_utf8_lossy(s).trim().to_string())
+        .collect();
+    let string = |offset: usize| -> Option<String> {
+        byte(offset)
+            .filter(|index| *index != 0)
+            .and_then(|index| strings.get(index as usize - 1).cloned())
+            .filter(|s| !s.is_empty() && !s.eq_ignore_ascii_case("unknown") && !s.eq_ignore_ascii_case("not specified"))
+    };
+
+    // 0 = yuva boş, 0xFFFF = bilinmiyor, 0x7FFF = genişletilmiş boyut alanında, bit 15 = KB birimi.
+    // Genişletilmiş alan SMBIOS 2.7 ile geldi; daha kısa kayıtlarda bu ofset dize alanına düşer
+    let size_mb = match word(0x0C)? {
+        0 | 0xFFFF => return None,
+        0x7FFF => dword(0x1C)? as u64 & 0x7FFF_FFFF,
+        size if size & 0x8000 != 0 => (size & 0x7FFF) as u64 / 1024,
+        size => size as u64,
+    };
+
+    Some(MemoryModule {
+        locator: string(0x10),
+        bank: string(0x11),
+        size_mb,
+        memory_type: byte(0x12).and_then(memory_type_name).map(String::from),
+        speed_mts: word(0x15).filter(|s| *s != 0 && *s != 0xFFFF),
+        manufacturer: string(0x17),
+        serial: string(0x18),
+        part_number: string(0x1A),
+    })
+}
+
+fn memory_type_name(code: u8) -> Option<&'static str> {
+    Some(match code {
+        0x12 => "DDR",
+        0x13 => "DDR2",
+        0x18 => "DDR3",
+        0x1A => "DDR4",
+        0x1B => "LPDDR",
+        0x1C => "LPDDR2",
+        0x1D => "LPDDR3",
+        0x1E => "LPDDR4",
+        0x22 => "DDR5",
+        0x23 => "LPDDR5",
+        0x07 => "RAM",
+        _ => return None,
+    })
+}
+
+pub fn get_block_devices(config: &SmartConfig) -> Vec<BlockDevice> {
+    let mut last = BLOCK_DEVICES.lock().unwrap_or_else(|e| e.into_inner());
+    let interval = Duration::from_secs(config.interval_minutes * 60);
+    if let Some((at, devices)) = last.as_ref() {
+        if at.elapsed() < interval {
+            return devices.clone();
+        }
+    }
+
+    let devices = read_block_devices();
+    *last = Some((Instant::now(), devices.clone()));
+    devices
+}
+
+fn read_block_devices() -> Vec<BlockDevice> {
+    let Ok(entries) = fs::read_dir("/sys/block") else {
+        return Vec::new();
+    };
+
+    let mut devices: Vec<BlockDevice> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            // Sanal aygıtlar (loop, ram, zram) envantere girmez
+            if ["loop", "ram", "zram"].iter().any(|prefix| name.starts_with(prefix)) {
+                return None;
+            }
+            let dir = entry.path();
+            let sectors: u64 = read_trimmed(&dir.join("size"))?.parse().ok()?;
+            Some(BlockDevice {
+                model: read_trimmed(&dir.join("device/model")),
+                vendor: read_trimmed(&dir.join("device/vendor")),
+                serial: read_trimmed(&dir.join("device/serial"))
+                    .or_else(|| read_trimmed(&dir.join("device/wwid"))),
+                // size dosyası her zaman 512 baytlık sektör sayısıdır
+                size_bytes: sectors * 512,
+                rotational: read_trimmed(&dir.join("queue/rotational")).map(|v| v == "1"),
+                removable: read_trimmed(&dir.join("removable")).map(|v| v == "1").unwrap_or(false),
+                transport: transport(&dir, &name),
+                name,
+            })
+        })
+        .collect();
+    devices.sort_by(|a, b| a.name.cmp(&b.name));
+    devices
+}
+
+// Aygıtın sysfs yolundan bağlı olduğu veri yolunu çıkarır
+fn transport(dir: &Path, name: &str) -> Option<String> {
+    if name.starts_with("dm-") || name.starts_with("md") {
+        return None;
+    }
+    let path = fs::canonicalize(dir).ok()?.to_string_lossy().into_owned();
+    let kind = if name.starts_with("nvme") || path.contains("/nvme") {
+        "nvme"
+    } else if path.contains("/usb") {
+        "usb"
+    } else if path.contains("/virtio") {
+        "virtio"
+    } else if path.contains("/ata") {
+        "sata"
+    } else if path.contains("/mmc") {
+        "mmc"
+    } else if path.contains("/host") {
+        "scsi"
+    } else {
+        return None;
+    };
+    Some(kind.to_string())
+}
+
+fn read_trimmed(path: &Path) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_memory_devices() {
+        let module = parse_memory_device(include_bytes!("../tests/fixtures/hardware/17-0.raw")).unwrap();
+        assert_eq!(module.locator.as_deref(), Some("P1-DIMMA1"));
+        assert_eq!(module.bank.as_deref(), Some("P0_Node0_Channel0_Dimm0"));
+        assert_eq!(module.size_mb, 16384);
+        assert_eq!(module.memory_type.as_deref(), Some("DDR4"));
+        assert_eq!(module.speed_mts, Some(3200));
+        assert_eq!(module.manufacturer.as_deref(), Some("Samsung"));
+        assert_eq!(module.serial.as_deref(), Some("03A4B7C2"));
+        assert_eq!(module.part_number.as_deref(), Some("M393A2K43DB3-CWE"));
+
+        // 32 GB üzeri modüller boyutu genişletilmiş alanda verir
+        let module = parse_memory_device(include_bytes!("../tests/fixtures/hardware/17-1.raw")).unwrap();
+        assert_eq!(module.size_mb, 131072);
+        assert_eq!(module.memory_type.as_deref(), Some("DDR5"));
+        assert_eq!(module.manufacturer.as_deref(), Some("Micron Technology"));
+    }
+
+    #[test]
+    fn skips_empty_and_truncated_devices() {
+        // Genişletilmiş alanı içermeyen kayıt 0x7FFF bildirse de dize alanı boyut diye okunmaz
+        assert!(parse_memory_device(include_bytes!("../tests/fixtures/hardware/17-2.raw")).is_none());
+        // Boş yuva
+        assert!(parse_memory_device(include_bytes!("../tests/fixtures/hardware/17-3.raw")).is_none());
+        assert!(parse_memory_device(&[17, 0x28, 0, 0]).is_none());
+    }
+}
@@ -0,0 +1,395 @@
+use crate::config::InventoryConfig;
+use crate::packages::PackageManager;
+use log::warn;
+use nix::unistd::{access, AccessFlags};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+// Envanter pahalı bir işlem, her döngüde değil belirli aralıklarla alınır; arada son sonuç gönderilir
+static LAST_INVENTORY: Mutex<Option<(Instant, InventoryInfo)>> = Mutex::new(None);
+
+// Son alınan envanter; dosyaya yazılamasa da aynı değişiklikler tekrar raporlanmaz
+static LAST_SNAPSHOT: Mutex<Option<Snapshot>> = Mutex::new(None);
+
+static STATE_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InstalledPackage {
+    pub name: String,
+    pub version: String,
+    pub arch: String,
+    pub source: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    Installed,
+    Removed,
+    Upgraded,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PackageChange {
+    pub name: String,
+    pub arch: String,
+    pub change: ChangeKind,
+    pub old_version: Option<String>,
+    pub new_version: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct InventoryInfo {
+    pub manager: PackageManager,
+    pub package_count: usize,
+    pub packages: Vec<InstalledPackage>,
+    pub changes: Vec<PackageChange>,
+    pub previous_snapshot: Option<String>,
+    pub timestamp: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Snapshot {
+    timestamp: String,
+    packages: Vec<InstalledPackage>,
+}
+
+pub fn get_inventory_info(pkg_manager: PackageManager, config: &InventoryConfig) -> Option<InventoryInfo> {
+    if !config.enabled || pkg_manager == PackageManager::Unknown {
+        return None;
+    }
+
+    let mut last = LAST_INVENTORY.lock().unwrap_or_else(|e| e.into_inner());
+    let interval = Duration::from_secs(config.interval_minutes * 60);
+    if let Some((at, inventory)) = last.as_ref() {
+        if at.elapsed() < interval {
+            return Some(inventory.clone());
+        }
+    }
+
+    let inventory = take_inventory(pkg_manager, config)?;
+    *last = Some((Instant::now(), inventory.clone()));
+    Some(inventory)
+}
+
+fn take_inventory(pkg_manager: PackageManager, config: &InventoryConfig) -> Option<InventoryInfo> {
+    let packages = match pkg_manager {
+        PackageManager::Apt => fs::read_to_string("/var/lib/dpkg/status")
+            .map(|content| parse_dpkg_status(&content))
+            .unwrap_or_default(),
+        PackageManager::Yum | PackageManager::Dnf | PackageManager::Zypper => get_rpm_packages(),
+        PackageManager::Pacman => get_pacman_packages(Path::new("/var/lib/pacman/local")),
+        PackageManager::Unknown => return None,
+    };
+
+    let timestamp = chrono::Local::now().to_rfc3339();
+    let snapshot_path = resolve_state_path(&config.snapshot_path);
+
+    // Önceki envanter ile karşılaştır; bellekteki kopya dosyadakinden her zaman daha yenidir
+    let mut last_snapshot = LAST_SNAPSHOT.lock().unwrap_or_else(|e| e.into_inner());
+    let previous: Option<Snapshot> = last_snapshot.take().or_else(|| {
+        fs::read_to_string(&snapshot_path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+    });
+    let changes = previous
+        .as_ref()
+        .map(|prev| diff_packages(&prev.packages, &packages))
+        .unwrap_or_default();
+
+    let snapshot = Snapshot {
+        timestamp: timestamp.clone(),
+        packages,
+    };
+    let saved = serde_json::to_string(&snapshot)
+        .map_err(anyhow::Error::from)
+        .and_then(|json| Ok(fs::write(&snapshot_path, json)?));
+    if let Err(e) = saved {
+        warn!("Paket envanteri kaydedilemedi ({}): {}", snapshot_path.display(), e);
+    }
+    *last_snapshot = Some(snapshot.clone());
+
+    Some(InventoryInfo {
+        manager: pkg_manager,
+        package_count: snapshot.packages.len(),
+        packages: snapshot.packages,
+        changes,
+        previous_snapshot: previous.map(|p| p.timestamp),
+        timestamp,
+    })
+}
+
+// Göreli yollar durum dizinine göre çözülür; daemon / dizininde nobody olarak çalışır
+fn resolve_state_path(path: &str) -> PathBuf {
+    let path = Path::new(path);
+    if path.is_absolute() {
+        return path.to_path_buf();
+    }
+    state_dir().join(path)
+}
+
+// systemd StateDirectory=, /var/lib/staffmon veya geçici dizin; ilk yazılabilir olan kullanılır
+fn state_dir() -> &'static Path {
+    STATE_DIR.get_or_init(|| {
+        let candidates = std::env::var_os("STATE_DIRECTORY")
+            .map(PathBuf::from)
+            .into_iter()
+            .chain([PathBuf::from("/var/lib/staffmon"), std::env::temp_dir().join("staffmon")]);
+        for dir in candidates {
+            if fs::create_dir_all(&dir).is_ok() && access(&dir, AccessFlags::W_OK).is_ok() {
+                return dir;
+            }
+        }
+        std::env::temp_dir()
+    })
+}
+
+// Aynı ad ve mimariden birden çok sürüm kurulu olabilir (kernel, kernel-core); sürüm kümeleri karşılaştırılır
+fn diff_packages(old: &[InstalledPackage], new: &[InstalledPackage]) -> Vec<PackageChange> {
+    fn versions(packages: &[InstalledPackage]) -> BTreeMap<(String, String), BTreeSet<String>> {
+        let mut map: BTreeMap<(String, String), BTreeSet<String>> = BTreeMap::new();
+        for p in packages {
+            map.entry((p.name.clone(), p.arch.clone())).or_default().insert(p.version.clone());
+        }
+        map
+    }
+    let old_map = versions(old);
+    let new_map = versions(new);
+    let empty = BTreeSet::new();
+
+    let mut changes = Vec::new();
+    let keys: BTreeSet<&(String, String)> = old_map.keys().chain(new_map.keys()).collect();
+    for key @ (name, arch) in keys {
+        let old_versions = old_map.get(key).unwrap_or(&empty);
+        let new_versions = new_map.get(key).unwrap_or(&empty);
+        let removed: Vec<&String> = old_versions.difference(new_versions).collect();
+        let installed: Vec<&String> = new_versions.difference(old_versions).collect();
+
+        let change = |change, old_version: Option<&String>, new_version: Option<&String>| PackageChange {
+            name: name.clone(),
+            arch: arch.clone(),
+            change,
+            old_version: old_version.cloned(),
+            new_version: new_version.cloned(),
+        };
+        // Tek sürüm yerini başka bir sürüme bıraktıysa yükseltmedir; diğer durumlar ayrı ayrı raporlanır
+        if let ([old_version], [new_version]) = (removed.as_slice(), installed.as_slice()) {
+            changes.push(change(ChangeKind::Upgraded, Some(old_version), Some(new_version)));
+            continue;
+        }
+        for version in installed {
+            changes.push(change(ChangeKind::Installed, None, Some(version)));
+        }
+        for version in removed {
+            changes.push(change(ChangeKind::Removed, Some(version), None));
+        }
+    }
+    changes
+}
+
+// dpkg durum veritabanı boş satırlarla ayrılmış kayıtlardan oluşur
+fn parse_dpkg_status(content: &str) -> Vec<InstalledPackage> {
+    let mut packages = Vec::new();
+
+    for stanza in content.split("\n\n") {
+        let mut fields: BTreeMap<&str, &str> = BTreeMap::new();
+        for line in stanza.lines() {
+            if line.starts_with(char::is_whitespace) {
+                continue;
+            }
+            if let Some((key, value)) = line.split_once(':') {
+                fields.insert(key, value.trim());
+            }
+        }
+
+        let installed = fields.get("Status").map(|s| s.ends_with(" installed")).unwrap_or(false);
+        if let (true, Some(name), Some(version)) = (installed, fields.get("Package"), fields.get("Version")) {
+            packages.push(InstalledPackage {
+                name: name.to_string(),
+                version: version.to_string(),
+                arch: fields.get("Architecture").unwrap_or(&"").to_string(),
+                // "Source: openssl (3.0.2-0ubuntu1)" biçiminde sürüm içerebilir
+                source: fields
+                    .get("Source")
+                    .and_then(|s| s.split_whitespace().next())
+                    .map(String::from),
+            });
+        }
+    }
+    packages
+}
+
+fn get_rpm_packages() -> Vec<InstalledPackage> {
+    Command::new("rpm")
+        .args([
+            "-qa",
+            "--qf",
+            "%{NAME}\\t%|EPOCH?{%{EPOCH}:}:{}|%{VERSION}-%{RELEASE}\\t%{ARCH}\\t%{SOURCERPM}\\n",
+        ])
+        .output()
+        .map(|output| parse_rpm_query(&String::from_utf8_lossy(&output.stdout)))
+        .unwrap_or_default()
+}
+
+// kernel-core\t4.18.0-513.24.1.el8_9\tx86_64\tkernel-4.18.0-513.24.1.el8_9.src.rpm
+fn parse_rpm_query(output: &str) -> Vec<InstalledPackage> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split('\t').collect();
+            if fields.len() < 4 {
+                return None;
+            }
+            Some(InstalledPackage {
+                name: fields[0].to_string(),
+                version: fields[1].to_string(),
+                arch: fields[2].to_string(),
+                source: Some(fields[3].to_string()).filter(|s| s != "(none)"),
+            })
+        })
+        .collect()
+}
+
+// pacman yerel veritabanı: her paket için <ad>-<sürüm>/desc dosyası
+fn get_pacman_packages(db_path: &Path) -> Vec<InstalledPackage> {
+    let entries = match fs::read_dir(db_path) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut packages: Vec<InstalledPackage> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| fs::read_to_string(e.path().join("desc")).ok())
+        .filter_map(|desc| {
+            let mut fields: BTreeMap<&str, &str> = BTreeMap::new();
+            let mut lines = desc.lines();
+            while let Some(line) = lines.next() {
+                if line.starts_with('%') && line.ends_with('%') {
+                    if let Some(value) = lines.next() {
+                        fields.insert(line.trim_matches('%'), value);
+                    }
+                }
+            }
+            Some(InstalledPackage {
+                name: fields.get("NAME")?.to_string(),
+                version: fields.get("VERSION")?.to_string(),
+                arch: fields.get("ARCH").unwrap_or(&"").to_string(),
+                source: fields.get("BASE").map(|s| s.to_string()),
+            })
+        })
+        .collect();
+    packages.sort_by(|a, b| a.name.cmp(&b.name));
+    packages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn package(name: &str, version: &str) -> InstalledPackage {
+        InstalledPackage {
+            name: name.to_string(),
+            version: version.to_string(),
+            arch: "x86_64".to_string(),
+            source: None,
+        }
+    }
+
+    fn summary(changes: &[PackageChange]) -> Vec<String> {
+        changes
+            .iter()
+            .map(|c| {
+                format!(
+                    "{:?} {} {}->{}",
+                    c.change,
+                    c.name,
+                    c.old_version.as_deref().unwrap_or("-"),
+                    c.new_version.as_deref().unwrap_or("-")
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parallel_versions_are_stable_across_order() {
+        let old = vec![package("kernel-core", "4.18.0-513.11.1"), package("kernel-core", "4.18.0-513.24.1")];
+        let new = vec![package("kernel-core", "4.18.0-513.24.1"), package("kernel-core", "4.18.0-513.11.1")];
+        assert!(diff_packages(&old, &new).is_empty());
+    }
+
+    #[test]
+    fn reports_installs_removals_and_upgrades() {
+        let old = vec![
+            package("kernel-core", "4.18.0-513.11.1"),
+            package("kernel-core", "4.18.0-513.24.1"),
+            package("openssl-libs", "1:1.1.1k-9"),
+            package("telnet", "0.17-76"),
+        ];
+        let new = vec![
+            package("kernel-core", "4.18.0-553.5.1"),
+            package("kernel-core", "4.18.0-513.24.1"),
+            package("openssl-libs", "1:1.1.1k-12"),
+            package("vim-minimal", "8.0.1763-19"),
+        ];
+        assert_eq!(
+            summary(&diff_packages(&old, &new)),
+            vec![
+                "Upgraded kernel-core 4.18.0-513.11.1->4.18.0-553.5.1",
+                "Upgraded openssl-libs 1:1.1.1k-9->1:1.1.1k-12",
+                "Removed telnet 0.17-76->-",
+                "Installed vim-minimal -->8.0.1763-19",
+            ]
+        );
+
+        // Yeni çekirdek eskisini kaldırmadan kurulduysa yükseltme değil kurulumdur
+        let new = vec![
+            package("kernel-core", "4.18.0-513.11.1"),
+            package("kernel-core", "4.18.0-513.24.1"),
+            package("kernel-core", "4.18.0-553.5.1"),
+        ];
+        let changes = diff_packages(&old[..2], &new);
+        assert_eq!(summary(&changes), vec!["Installed kernel-core -->4.18.0-553.5.1"]);
+    }
+
+    #[test]
+    fn parses_dpkg_status() {
+        let packages = parse_dpkg_status(include_str!("../tests/fixtures/inventory/dpkg_status"));
+        let names: Vec<&str> = packages.iter().map(|p| p.name.as_str()).collect();
+        // Yalnızca yapılandırma dosyaları kalan paketler kurulu sayılmaz
+        assert_eq!(names, vec!["libssl3", "linux-image-5.15.0-91-generic", "tzdata"]);
+        assert_eq!(packages[0].version, "3.0.2-0ubuntu1.15");
+        assert_eq!(packages[0].arch, "amd64");
+        assert_eq!(packages[0].source.as_deref(), Some("openssl"));
+        assert_eq!(packages[1].source.as_deref(), Some("linux-signed"));
+        assert_eq!(packages[2].arch, "all");
+        assert_eq!(packages[2].source, None);
+    }
+
+    #[test]
+    fn parses_rpm_query() {
+        let packages = parse_rpm_query(include_str!("../tests/fixtures/inventory/rpm_qa.txt"));
+        assert_eq!(packages.len(), 4);
+        assert_eq!(packages[0].name, "kernel-core");
+        assert_eq!(packages[1].version, "4.18.0-513.11.1.el8_9");
+        assert_eq!(packages[2].version, "1:1.1.1k-12.el8_9");
+        assert_eq!(packages[2].source.as_deref(), Some("openssl-1.1.1k-12.el8_9.src.rpm"));
+        assert_eq!(packages[3].source, None);
+    }
+
+    #[test]
+    fn reads_pacman_local_database() {
+        let packages = get_pacman_packages(&Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/inventory/pacman_local"));
+        assert_eq!(packages.len(), 2);
+        assert_eq!(packages[0].name, "linux");
+        assert_eq!(packages[0].version, "6.6.8.arch1-1");
+        assert_eq!(packages[0].source.as_deref(), Some("linux"));
+        assert_eq!(packages[1].name, "openssl");
+        assert_eq!(packages[1].arch, "x86_64");
+        assert_eq!(packages[1].source, None);
+    }
+}
@@ -0,0 +1,256 @@
+use crate::config::KernelConfig;
+use crate::{Finding, Severity};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+const SECURE_BOOT_VAR: &str =
+    "/sys/firmware/efi/efivars/SecureBoot-8be4df61-93ca-11d2-aa0d-00e098032b8c";
+
+#[derive(Debug, Serialize)]
+pub struct KernelSecurityInfo {
+    pub sysctls: Vec<SysctlValue>,
+    pub selinux: Option<String>,
+    pub apparmor: Option<AppArmorInfo>,
+    pub secure_boot: Option<bool>,
+    pub loaded_modules: Vec<String>,
+    pub findings: Vec<Finding>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SysctlValue {
+    pub name: String,
+    pub value: Option<String>,
+    pub expected: String,
+    pub compliant: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AppArmorInfo {
+    pub enabled: bool,
+    pub profiles_enforce: Option<u32>,
+    pub profiles_complain: Option<u32>,
+}
+
+pub fn get_kernel_security_info(config: &KernelConfig) -> Option<KernelSecurityInfo> {
+    if !config.enabled || crate::virt::in_container() {
+        return None;
+    }
+
+    // Sysctl değerlerini /proc/sys altından oku
+    let (sysctls, mut findings) = check_sysctls(&config.expected_sysctls, read_sysctl);
+
+    let selinux = get_selinux_mode();
+    let apparmor = get_apparmor_info();
+
+    let mac_enforcing = selinux.as_deref() == Some("enforcing")
+        || apparmor.as_ref().map(|a| a.enabled).unwrap_or(false);
+    if config.require_mac && !mac_enforcing {
+        findings.push(Finding {
+            check: "kernel.mac".to_string(),
+            severity: Severity::Warning,
+            message: "SELinux veya AppArmor zorunlu modda değil".to_string(),
+            expected: Some("enforcing".to_string()),
+            actual: selinux.clone(),
+        });
+    }
+
+    let secure_boot = get_secure_boot_state();
+    if config.require_secure_boot && secure_boot != Some(true) {
+        findings.push(Finding {
+            check: "kernel.secure_boot".to_string(),
+            severity: Severity::Warning,
+            message: "Secure Boot etkin değil".to_string(),
+            expected: Some("enabled".to_string()),
+            actual: Some(match secure_boot {
+                Some(false) => "disabled".to_string(),
+                _ => "unavailable".to_string(),
+            }),
+        });
+    }
+
+    let loaded_modules = get_loaded_modules();
+    for module in loaded_modules.iter().filter(|m| config.denied_modules.contains(m)) {
+        findings.push(Finding {
+            check: format!("kernel.module.{}", module),
+            severity: Severity::Warning,
+            message: format!("İstenmeyen çekirdek modülü yüklü: {}", module),
+            expected: Some("not loaded".to_string()),
+            actual: Some("loaded".to_string()),
+        });
+    }
+
+    Some(KernelSecurityInfo {
+        sysctls,
+        selinux,
+        apparmor,
+        secure_boot,
+        loaded_modules,
+        findings,
+    })
+}
+
+fn check_sysctls(
+    expected: &BTreeMap<String, String>,
+    read: impl Fn(&str) -> Option<String>,
+) -> (Vec<SysctlValue>, Vec<Finding>) {
+    let mut findings = Vec::new();
+    let sysctls = expected
+        .iter()
+        .map(|(name, expected)| {
+            let value = read(name);
+            // Bu çekirdekte olmayan ayarlar uyumsuz sayılmaz
+            let compliant = value
+                .as_deref()
+                .map(|v| expected.split('|').any(|e| e.trim() == v))
+                .unwrap_or(true);
+            if !compliant {
+                findings.push(Finding {
+                    check: format!("sysctl.{}", name),
+                    severity: Severity::Warning,
+                    message: format!("{} beklenen değerde değil", name),
+                    expected: Some(expected.clone()),
+                    actual: value.clone(),
+                });
+            }
+            SysctlValue {
+                name: name.clone(),
+                value,
+                expected: expected.clone(),
+                compliant,
+            }
+        })
+        .collect();
+    (sysctls, findings)
+}
+
+fn read_sysctl(name: &str) -> Option<String> {
+    let path = Path::new("/proc/sys").join(name.replace('.', "/"));
+    fs::read_to_string(path).ok().map(|content| sysctl_value(&content))
+}
+
+// Çok değerli ayarlar sekmeyle ayrılır ("32768\t60999"); tek boşlukla birleştirilir
+fn sysctl_value(content: &str) -> String {
+    content.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn get_selinux_mode() -> Option<String> {
+    if let Ok(enforce) = fs::read_to_string("/sys/fs/selinux/enforce") {
+        return Some(if enforce.trim() == "1" { "enforcing" } else { "permissive" }.to_string());
+    }
+    // selinuxfs bağlı değilse yapılandırma dosyasına bak
+    fs::read_to_string("/etc/selinux/config").ok().map(|_| "disabled".to_string())
+}
+
+fn get_apparmor_info() -> Option<AppArmorInfo> {
+    let enabled = fs::read_to_string("/sys/module/apparmor/parameters/enabled").ok()?;
+
+    // Profil listesi genellikle sadece root tarafından okunabilir
+    let profiles = fs::read_to_string("/sys/kernel/security/apparmor/profiles").ok();
+    Some(AppArmorInfo {
+        enabled: enabled.trim() == "Y",
+        profiles_enforce: profiles.as_deref().map(|p| count_profiles(p, "enforce")),
+        profiles_complain: profiles.as_deref().map(|p| count_profiles(p, "complain")),
+    })
+}
+
+// "/usr/sbin/cupsd (enforce)" satırları
+fn count_profiles(profiles: &str, mode: &str) -> u32 {
+    let suffix = format!("({})", mode);
+    profiles.lines().filter(|line| line.trim_end().ends_with(&suffix)).count() as u32
+}
+
+fn get_secure_boot_state() -> Option<bool> {
+    if !Path::new("/sys/firmware/efi").exists() {
+        return None;
+    }
+    fs::read(SECURE_BOOT_VAR).ok().and_then(|data| parse_secure_boot(&data))
+}
+
+// efivar içeriği: 4 bayt öznitelik + 1 bayt değer
+fn parse_secure_boot(data: &[u8]) -> Option<bool> {
+    data.get(4).map(|v| *v == 1)
+}
+
+fn get_loaded_modules() -> Vec<String> {
+    fs::read_to_string("/proc/modules")
+        .map(|content| parse_modules(&content))
+        .unwrap_or_default()
+}
+
+// "sctp 434176 4 - Live 0x0000000000000000": ilk alan modül adıdır
+fn parse_modules(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .filter_map(|line| line.split_whitespace().next())
+        .map(String::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // "sysctl -a" biçimindeki örnekten ad -> değer haritası
+    fn sysctl_fixture() -> BTreeMap<String, String> {
+        include_str!("../tests/fixtures/kernel/sysctl.txt")
+            .lines()
+            .filter_map(|line| line.split_once(" = "))
+            .map(|(name, value)| (name.to_string(), sysctl_value(value)))
+            .collect()
+    }
+
+    #[test]
+    fn checks_sysctl_compliance() {
+        let values = sysctl_fixture();
+        let expected: BTreeMap<String, String> = [
+            ("net.ipv4.ip_forward", "0"),
+            ("net.ipv4.conf.all.rp_filter", "1|2"),
+            ("kernel.kptr_restrict", "1|2"),
+            ("kernel.yama.ptrace_scope", "1 | 2 | 3"),
+            ("net.ipv4.ip_local_port_range", "32768 60999"),
+            ("kernel.unprivileged_bpf_disabled", "1|2"),
+        ]
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+
+        let (sysctls, findings) = check_sysctls(&expected, |name| values.get(name).cloned());
+        let compliant: BTreeMap<&str, bool> = sysctls.iter().map(|s| (s.name.as_str(), s.compliant)).collect();
+        assert!(!compliant["net.ipv4.ip_forward"]);
+        // "|" ile ayrılan alternatiflerden biri yeterlidir, boşluklar yok sayılır
+        assert!(compliant["net.ipv4.conf.all.rp_filter"]);
+        assert!(!compliant["kernel.kptr_restrict"]);
+        assert!(compliant["kernel.yama.ptrace_scope"]);
+        // Sekmeyle ayrılmış çok değerli ayar
+        assert!(compliant["net.ipv4.ip_local_port_range"]);
+        // Çekirdekte olmayan ayar uyumsuz sayılmaz
+        assert!(compliant["kernel.unprivileged_bpf_disabled"]);
+        assert_eq!(sysctls.iter().find(|s| s.name == "kernel.unprivileged_bpf_disabled").unwrap().value, None);
+
+        assert_eq!(findings.len(), 2);
+        let finding = findings.iter().find(|f| f.check == "sysctl.kernel.kptr_restrict").unwrap();
+        assert_eq!(finding.expected.as_deref(), Some("1|2"));
+        assert_eq!(finding.actual.as_deref(), Some("0"));
+        assert!(findings.iter().any(|f| f.check == "sysctl.net.ipv4.ip_forward" && f.actual.as_deref() == Some("1")));
+    }
+
+    #[test]
+    fn parses_secure_boot_efivar() {
+        assert_eq!(parse_secure_boot(include_bytes!("../tests/fixtures/kernel/SecureBoot-enabled")), Some(true));
+        assert_eq!(parse_secure_boot(include_bytes!("../tests/fixtures/kernel/SecureBoot-disabled")), Some(false));
+        // Yalnızca öznitelik baytları okunabildiyse durum bilinmez
+        assert_eq!(parse_secure_boot(&[0x06, 0, 0, 0]), None);
+    }
+
+    #[test]
+    fn parses_modules_and_apparmor_profiles() {
+        let modules = parse_modules(include_str!("../tests/fixtures/kernel/modules.txt"));
+        assert_eq!(modules, vec!["sctp", "tls", "nf_tables", "ext4"]);
+
+        let profiles = include_str!("../tests/fixtures/kernel/apparmor_profiles.txt");
+        assert_eq!(count_profiles(profiles, "enforce"), 3);
+        assert_eq!(count_profiles(profiles, "complain"), 1);
+    }
+}
@@ -0,0 +1,395 @@
+use crate::config::{LogWatch, LogsConfig};
+use anyhow::{bail, Result};
+use log::warn;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{ErrorKind, Read, Seek, SeekFrom};
+use std::os::unix::fs::MetadataExt;
+use std::process::Command;
+use std::sync::Mutex;
+
+const MAX_SAMPLE_LENGTH: usize = 500;
+
+// İzleme adı -> açık dosya ve okunan konum; izleme ilk kez çalıştığında kayıt yoktur
+static FILES: Mutex<BTreeMap<String, TailState>> = Mutex::new(BTreeMap::new());
+
+// İzleme adı -> journal'da kalınan yer
+static JOURNAL: Mutex<BTreeMap<String, JournalPosition>> = Mutex::new(BTreeMap::new());
+
+// Desenler ilk kullanımda derlenir; geçersiz desen bir kez loglanır
+static PATTERNS: Mutex<BTreeMap<String, Option<Regex>>> = Mutex::new(BTreeMap::new());
+
+enum TailState {
+    // Dosya henüz yok; oluşturulduğunda baştan okunur
+    Missing,
+    Open(OpenFile),
+}
+
+struct OpenFile {
+    file: File,
+    inode: u64,
+    device: u64,
+    offset: u64,
+    // Satır sonu henüz yazılmamış son kısım
+    partial: Vec<u8>,
+}
+
+enum JournalPosition {
+    Cursor(String),
+    // Henüz imleç alınmadıysa izlemenin başladığı an (Unix zamanı)
+    Since(i64),
+}
+
+#[derive(Debug, Serialize)]
+pub struct LogWatchResult {
+    pub name: String,
+    // Dosya yolu veya "journal"
+    pub source: String,
+    // Son döngüden bu yana okunan satırlar
+    pub lines_read: u64,
+    pub matches: u64,
+    pub pattern_matches: BTreeMap<String, u64>,
+    // Eşleşen son satırlar
+    pub samples: Vec<String>,
+    pub rotated: bool,
+    pub truncated: bool,
+    pub error: Option<String>,
+}
+
+// Her izleme için son çağrıdan bu yana eklenen satırları sayar
+pub fn get_log_watches(config: &LogsConfig) -> Vec<LogWatchResult> {
+    if !config.enabled {
+        return Vec::new();
+    }
+
+    config
+        .watches
+        .iter()
+        .map(|watch| {
+            let mut result = LogWatchResult {
+                name: watch.name.clone(),
+                source: watch.path.clone().unwrap_or_else(|| "journal".to_string()),
+                lines_read: 0,
+                matches: 0,
+                pattern_matches: watch.patterns.iter().map(|p| (p.clone(), 0)).collect(),
+                samples: Vec::new(),
+                rotated: false,
+                truncated: false,
+                error: None,
+            };
+            let lines = match &watch.path {
+                Some(path) => read_file(&watch.name, path, config.max_read_bytes, &mut result),
+                None => read_journal(watch),
+            };
+            match lines {
+                Ok(lines) => count_matches(watch, &lines, config.max_samples, &mut result),
+                Err(e) => result.error = Some(e.to_string()),
+            }
+            result
+        })
+        .collect()
+}
+
+fn count_matches(watch: &LogWatch, lines: &[String], max_samples: usize, result: &mut LogWatchResult) {
+    let patterns: Vec<(&String, Regex)> = watch
+        .patterns
+        .iter()
+        .filter_map(|pattern| compiled(pattern).map(|regex| (pattern, regex)))
+        .collect();
+
+    for line in lines {
+        result.lines_read += 1;
+        let mut matched = watch.patterns.is_empty();
+        for (pattern, regex) in &patterns {
+            if regex.is_match(line) {
+                *result.pattern_matches.entry(pattern.to_string()).or_default() += 1;
+                matched = true;
+            }
+        }
+        if !matched {
+            continue;
+        }
+        result.matches += 1;
+        if max_samples > 0 {
+            if result.samples.len() == max_samples {
+                result.samples.remove(0);
+            }
+            result.samples.push(line.chars().take(MAX_SAMPLE_LENGTH).collect());
+        }
+    }
+}
+
+fn compiled(pattern: &str) -> Option<Regex> {
+    let mut cache = PATTERNS.lock().unwrap_or_else(|e| e.into_inner());
+    cache
+        .entry(pattern.to_string())
+        .or_insert_with(|| match Regex::new(pattern) {
+            Ok(regex) => Some(regex),
+            Err(e) => {
+                warn!("Geçersiz günlük izleme deseni ({}): {}", pattern, e);
+                None
+            }
+        })
+        .clone()
+}
+
+fn read_file(key: &str, path: &str, max_bytes: u64, result: &mut LogWatchResult) -> Result<Vec<String>> {
+    let mut files = FILES.lock().unwrap_or_else(|e| e.into_inner());
+    let mut lines = Vec::new();
+
+    let mut open = match files.remove(key) {
+        // İlk döngüde geçmiş satırlar sayılmaz, dosyanın sonundan başlanır
+        None => {
+            let file = open_at(path, SeekFrom::End(0))?;
+            files.insert(key.to_string(), file.map_or(TailState::Missing, TailState::Open));
+            return Ok(lines);
+        }
+        Some(TailState::Missing) => match open_at(path, SeekFrom::Start(0)) {
+            Ok(Some(file)) => file,
+            other => {
+                files.insert(key.to_string(), TailState::Missing);
+                return other.map(|_| lines);
+            }
+        },
+        Some(TailState::Open(file)) => file,
+    };
+
+    // logrotate (create) sonrası yol başka bir inode'u gösterir veya dosya silinmiştir
+    let replaced = fs::metadata(path)
+        .map(|m| m.ino() != open.inode || m.dev() != open.device)
+        .unwrap_or(true);
+    if replaced {
+        // Döndürmeden önce eski dosyaya yazılmış satırlar hâlâ açık olan tanıtıcıdan okunur.
+        // Tanıtıcı bırakılınca kalan kısım kaybolacağından sınır parça parça tamamen okunur
+        while !read_new(&mut open, max_bytes, &mut lines) {}
+        if !open.partial.is_empty() {
+            lines.push(String::from_utf8_lossy(&open.partial).into_owned());
+        }
+        result.rotated = true;
+        match open_at(path, SeekFrom::Start(0)) {
+            Ok(Some(file)) => open = file,
+            other => {
+                files.insert(key.to_string(), TailState::Missing);
+                return other.map(|_| lines);
+            }
+        }
+    } else if open.file.metadata().map(|m| m.len() < open.offset).unwrap_or(false) {
+        // copytruncate veya "> dosya": boyut okunan konumun gerisine düştüyse baştan okunur
+        open.offset = 0;
+        open.partial.clear();
+        result.truncated = true;
+    }
+
+    read_new(&mut open, max_bytes, &mut lines);
+    files.insert(key.to_string(), TailState::Open(open));
+    Ok(lines)
+}
+
+// Dosya yoksa Ok(None) döner
+fn open_at(path: &str, position: SeekFrom) -> Result<Option<OpenFile>> {
+    let mut file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    let metadata = file.metadata()?;
+    let offset = file.seek(position)?;
+    Ok(Some(OpenFile {
+        file,
+        inode: metadata.ino(),
+        device: metadata.dev(),
+        offset,
+        partial: Vec::new(),
+    }))
+}
+
+// Kalınan konumdan en fazla max_bytes okur; tamamlanmamış son satır bir sonraki okumaya bırakılır.
+// Dosyanın sonuna ulaşıldıysa (veya okunamıyorsa) true döner
+fn read_new(open: &mut OpenFile, max_bytes: u64, lines: &mut Vec<String>) -> bool {
+    if open.file.seek(SeekFrom::Start(open.offset)).is_err() {
+        return true;
+    }
+    let mut buf = Vec::new();
+    // Okuma yarıda hata verse de okunan kısım buf'tadır
+    let at_end = (&open.file).take(max_bytes).read_to_end(&mut buf).is_err() || (buf.len() as u64) < max_bytes;
+    open.offset += buf.len() as u64;
+    open.partial.extend_from_slice(&buf);
+
+    let complete = match open.partial.iter().rposition(|b| *b == b'\n') {
+        Some(end) => open.partial.drain(..=end).collect::<Vec<u8>>(),
+        // Satır sonu hiç gelmeyen dev satırlar belleği doldurmasın
+        None if open.partial.len() as u64 >= max_bytes => std::mem::take(&mut open.partial),
+        None => return at_end,
+    };
+    lines.extend(
+        complete
+            .strip_suffix(b"\n")
+            .unwrap_or(&complete)
+            .split(|b| *b == b'\n')
+            .map(|line| String::from_utf8_lossy(line.strip_suffix(b"\r").unwrap_or(line)).into_owned()),
+    );
+    at_end
+}
+
+fn read_journal(watch: &LogWatch) -> Result<Vec<String>> {
+    let mut positions = JOURNAL.lock().unwrap_or_else(|e| e.into_inner());
+    let Some(position) = positions.get(&watch.name) else {
+        // İlk döngüde yalnızca bundan sonraki kayıtlar sayılmak üzere başlangıç anı kaydedilir
+        positions.insert(watch.name.clone(), JournalPosition::Since(chrono::Utc::now().timestamp()));
+        return Ok(Vec::new());
+    };
+
+    let mut command = Command::new("journalctl");
+    command.args(["-q", "--no-pager", "-o", "short-iso", "--show-cursor"]);
+    for unit in &watch.units {
+        command.args(["-u", unit]);
+    }
+    if let Some(priority) = &watch.priority {
+        command.args(["-p", priority]);
+    }
+    match position {
+        JournalPosition::Cursor(cursor) => command.arg(format!("--after-cursor={}", cursor)),
+        JournalPosition::Since(since) => command.arg(format!("--since=@{}", since)),
+    };
+
+    let output = command.output()?;
+    if !output.status.success() {
+        bail!("journalctl başarısız: {}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+
+    let mut lines = Vec::new();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        // Yeni kayıt yoksa imleç satırı da yazılmaz, önceki konum korunur
+        if let Some(cursor) = line.strip_prefix("-- cursor: ") {
+            positions.insert(watch.name.clone(), JournalPosition::Cursor(cursor.to_string()));
+            continue;
+        }
+        lines.push(line.to_string());
+    }
+    Ok(lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::OpenOptions;
+    use std::io::Write;
+
+    fn append(path: &std::path::Path, content: &str) {
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .unwrap()
+            .write_all(content.as_bytes())
+            .unwrap();
+    }
+
+    fn empty_result() -> LogWatchResult {
+        LogWatchResult {
+            name: "test".to_string(),
+            source: "test".to_string(),
+            lines_read: 0,
+            matches: 0,
+            pattern_matches: BTreeMap::new(),
+            samples: Vec::new(),
+            rotated: false,
+            truncated: false,
+            error: None,
+        }
+    }
+
+    fn tail(path: &std::path::Path) -> (Vec<String>, LogWatchResult) {
+        tail_with("tail-test", path, 1024 * 1024)
+    }
+
+    fn tail_with(key: &str, path: &std::path::Path, max_bytes: u64) -> (Vec<String>, LogWatchResult) {
+        let mut result = empty_result();
+        let lines = read_file(key, &path.to_string_lossy(), max_bytes, &mut result).unwrap();
+        (lines, result)
+    }
+
+    #[test]
+    fn follows_appends_truncation_and_rotation() {
+        let dir = std::env::temp_dir().join(format!("staffmon-logwatch-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("app.log");
+        append(&path, "eski satır\n");
+
+        // İlk döngüde mevcut içerik atlanır
+        assert!(tail(&path).0.is_empty());
+
+        append(&path, "ERROR bir\nbilgi\nERROR ya");
+        assert_eq!(tail(&path).0, vec!["ERROR bir", "bilgi"]);
+        append(&path, "rım\n");
+        assert_eq!(tail(&path).0, vec!["ERROR yarım"]);
+
+        // copytruncate
+        fs::write(&path, "kısa\n").unwrap();
+        let (lines, result) = tail(&path);
+        assert!(result.truncated);
+        assert_eq!(lines, vec!["kısa"]);
+
+        // create: eski dosyanın son satırları ve yeni dosyanın tamamı okunur
+        append(&path, "döndürme öncesi\n");
+        fs::rename(&path, dir.join("app.log.1")).unwrap();
+        append(&path, "yeni dosya\n");
+        let (lines, result) = tail(&path);
+        assert!(result.rotated);
+        assert_eq!(lines, vec!["döndürme öncesi", "yeni dosya"]);
+
+        // Dosya silinip sonradan oluşturulursa baştan okunur
+        fs::remove_file(&path).unwrap();
+        assert!(tail(&path).0.is_empty());
+        append(&path, "ilk\n");
+        assert_eq!(tail(&path).0, vec!["ilk"]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn drains_rotated_file_past_read_limit() {
+        let dir = std::env::temp_dir().join(format!("staffmon-logwatch-drain-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("app.log");
+        append(&path, "eski\n");
+        assert!(tail_with("drain-test", &path, 16).0.is_empty());
+
+        // Sınırın birkaç katı kadar satır yazıldıktan sonra döndürülür
+        let written: Vec<String> = (0..10).map(|i| format!("satır {:02}", i)).collect();
+        append(&path, &(written.join("\n") + "\nson"));
+        fs::rename(&path, dir.join("app.log.1")).unwrap();
+        append(&path, "yeni\n");
+
+        let (lines, result) = tail_with("drain-test", &path, 16);
+        assert!(result.rotated);
+        let mut expected = written.clone();
+        expected.extend(["son".to_string(), "yeni".to_string()]);
+        assert_eq!(lines, expected);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn counts_pattern_matches_and_keeps_last_samples() {
+        let watch = LogWatch {
+            name: "app".to_string(),
+            patterns: vec!["ERROR".to_string(), "(?i)timeout".to_string()],
+            ..Default::default()
+        };
+        let lines: Vec<String> = ["ERROR a", "ok", "ERROR Timeout b", "timeout c"]
+            .iter()
+            .map(|l| l.to_string())
+            .collect();
+        let mut result = empty_result();
+        count_matches(&watch, &lines, 2, &mut result);
+
+        assert_eq!(result.lines_read, 4);
+        assert_eq!(result.matches, 3);
+        assert_eq!(result.pattern_matches["ERROR"], 2);
+        assert_eq!(result.pattern_matches["(?i)timeout"], 2);
+        assert_eq!(result.samples, vec!["ERROR Timeout b", "timeout c"]);
+    }
+}
@@ -3,7 +3,7 @@
 use serde::Serialize;
 use std::process::Command;
 use sysinfo::{System, SystemExt, CpuExt, DiskExt, NetworkExt, NetworksExt};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use std::thread;
 use std::io;
 use std::fs;
@@ -11,19 +11,39 @@ use std::path::Path;
 use std::io::Write;
 use chrono;
 mod config;
+mod alerts;
 mod api;
+mod certs;
+mod cgroup;
+mod hardware;
+mod containers;
 mod log_config;
-use std::collections::HashMap;
+mod logwatch;
+mod inventory;
+mod kernel;
+mod oom;
+mod packages;
+mod process;
+mod reboot;
+mod smart;
+mod ssh;
+mod storage;
+mod systemd;
+mod timesync;
+mod version;
+mod virt;
+use std::collections::BTreeMap;
 use anyhow::Result;
 use daemonize::Daemonize;
-use log::{info, error, warn};
+use log::{debug, info, error, warn};
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Default, Serialize)]
 struct SystemInfo {
     cpu: CpuInfo,
     memory: MemoryInfo,
     load_avg: LoadAverage,
     disks: Vec<DiskInfo>,
+    storage: Option<storage::StorageHealth>,
     network: NetworkInfo,
     user_access: UserAccess,
     services: Vec<ServiceInfo>,
@@ -33,25 +53,39 @@ struct SystemInfo {
     hostname: String,
     kernel_version: String,
     os_version: String,
-    process_list: Vec<ProcessInfo>,
+    timezone: Option<String>,
+    time_sync: timesync::TimeSyncInfo,
+    certificates: Vec<certs::CertificateInfo>,
+    log_watches: Vec<logwatch::LogWatchResult>,
+    process_count: usize,
+    process_list: Vec<process::ProcessInfo>,
+    process_tree: Option<Vec<process::ProcessTreeNode>>,
+    process_findings: Vec<Finding>,
+    zombie_count: usize,
+    uninterruptible_count: usize,
+    stuck_processes: Vec<process::StuckProcess>,
+    oom_kills: Vec<oom::OomKill>,
+    containers: Vec<containers::ContainerInfo>,
+    package_inventory: Option<inventory::InventoryInfo>,
+    alerts: Vec<alerts::Alert>,
     timestamp: String,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Default, Serialize)]
 struct CpuInfo {
     usage_percent: f32,
     temperature_celsius: Option<f32>,
     frequency_mhz: f32,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Default, Serialize)]
 struct MemoryInfo {
     total_mb: u64,
     used_mb: u64,
     free_mb: u64,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Default, Serialize)]
 struct LoadAverage {
     one: f64,
     five: f64,
@@ -67,7 +101,7 @@ struct DiskInfo {
     mount_point: String,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Default, Serialize)]
 struct NetworkInfo {
     interfaces: Vec<NetworkInterface>,
 }
@@ -80,162 +114,146 @@ struct NetworkInterface {
     tx_bytes: u64,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Default, Serialize)]
 struct UserAccess {
     last_ssh_logins: Vec<String>,
     active_users: Vec<String>,
     sudo_users: Vec<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Default, Serialize)]
 struct ServiceInfo {
     name: String,
     active: bool,
     enabled: bool,
     version: Option<String>,
-}
-
-#[derive(Debug, Serialize)]
+    exec_path: Option<String>,
+    critical: bool,
+    active_state: Option<String>,
+    sub_state: Option<String>,
+    unit_file_state: Option<String>,
+    main_pid: Option<u32>,
+    memory_bytes: Option<u64>,
+    restarts: Option<u32>,
+    state_changed_at: Option<String>,
+    restarts_in_window: Option<u32>,
+    flapping: bool,
+    control_group: Option<String>,
+    resources: Option<cgroup::ServiceResources>,
+}
+
+#[derive(Debug, Default, Serialize)]
 struct SecurityInfo {
     firewall_enabled: bool,
     fail2ban_active: bool,
     open_ports: Vec<u16>,
-    package_updates: Vec<String>,
+    package_index: packages::PackageIndexInfo,
+    package_updates: Vec<packages::PackageUpdate>,
+    security_update_count: usize,
+    ssh: Option<ssh::SshInfo>,
+    kernel: Option<kernel::KernelSecurityInfo>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Severity {
+    Info,
+    Warning,
+    Critical,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct Finding {
+    check: String,
+    severity: Severity,
+    message: String,
+    expected: Option<String>,
+    actual: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize)]
 struct HardwareInfo {
     cpu_model: String,
     cores: u32,
     total_ram_mb: u64,
-    disk_info: Vec<String>,
     system_vendor: String,
     system_model: String,
+    system: hardware::SystemDmi,
+    memory_modules: Vec<hardware::MemoryModule>,
+    block_devices: Vec<hardware::BlockDevice>,
+    disk_health: Vec<smart::DiskHealth>,
+    virtualization: virt::VirtualizationInfo,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Default, Serialize)]
 struct UptimeInfo {
+    uptime_seconds: Option<u64>,
     current_uptime: String,
-    last_boot_time: String,
-    reboot_history: Vec<RebootRecord>,
-}
-
-#[derive(Debug, Serialize)]
-struct RebootRecord {
-    timestamp: String,
-    reason: Option<String>,
-}
-
-#[derive(Debug)]
-enum PackageManager {
-    Apt,
-    Yum,
-    Dnf,
-    Pacman,
-    Zypper,
-    Unknown,
-}
-
-fn detect_package_manager() -> PackageManager {
-    if Command::new("which").arg("apt").output().map(|o| o.status.success()).unwrap_or(false) {
-        PackageManager::Apt
-    } else if Command::new("which").arg("yum").output().map(|o| o.status.success()).unwrap_or(false) {
-        PackageManager::Yum
-    } else if Command::new("which").arg("dnf").output().map(|o| o.status.success()).unwrap_or(false) {
-        PackageManager::Dnf
-    } else if Command::new("which").arg("pacman").output().map(|o| o.status.success()).unwrap_or(false) {
-        PackageManager::Pacman
-    } else if Command::new("which").arg("zypper").output().map(|o| o.status.success()).unwrap_or(false) {
-        PackageManager::Zypper
+    last_boot_time: Option<String>,
+    reboot_history: Vec<reboot::BootRecord>,
+    // Bir önceki açılışın nasıl sona erdiği
+    last_shutdown: Option<reboot::ShutdownKind>,
+    reboot_required: bool,
+    reboot_reasons: Vec<String>,
+    deleted_library_processes: Vec<reboot::DeletedLibraryProcess>,
+}
+
+// /proc/stat btime: açılış zamanı (epoch saniyesi)
+fn boot_time() -> Option<i64> {
+    fs::read_to_string("/proc/stat")
+        .ok()?
+        .lines()
+        .find_map(|line| line.strip_prefix("btime "))
+        .and_then(|v| v.trim().parse().ok())
+}
+
+// 273600 -> "3d 4h 0m"
+fn format_duration(seconds: u64) -> String {
+    let (days, hours, minutes) = (seconds / 86400, seconds % 86400 / 3600, seconds % 3600 / 60);
+    if days > 0 {
+        format!("{}d {}h {}m", days, hours, minutes)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
     } else {
-        PackageManager::Unknown
+        format!("{}m", minutes)
     }
 }
 
-fn get_package_updates() -> Vec<String> {
-    let pkg_manager = detect_package_manager();
-    
-    match pkg_manager {
-        PackageManager::Apt => {
-            let _ = Command::new("apt")
-                .args(["update"])
-                .output();
-            
-            Command::new("apt")
-                .args(["list", "--upgradable"])
-                .output()
-                .map(|output| {
-                    String::from_utf8_lossy(&output.stdout)
-                        .lines()
-                        .skip(1)
-                        .map(String::from)
-                        .collect()
-                })
-                .unwrap_or_default()
-        },
-        PackageManager::Yum => {
-            Command::new("yum")
-                .args(["check-update"])
-                .output()
-                .map(|output| {
-                    String::from_utf8_lossy(&output.stdout)
-                        .lines()
-                        .filter(|line| !line.starts_with("Loaded plugins:") && !line.is_empty())
-                        .map(String::from)
-                        .collect()
-                })
-                .unwrap_or_default()
-        },
-        PackageManager::Dnf => {
-            Command::new("dnf")
-                .args(["check-update"])
-                .output()
-                .map(|output| {
-                    String::from_utf8_lossy(&output.stdout)
-                        .lines()
-                        .filter(|line| !line.starts_with("Last metadata") && !line.is_empty())
-                        .map(String::from)
-                        .collect()
-                })
-                .unwrap_or_default()
-        },
-        PackageManager::Pacman => {
-            Command::new("pacman")
-                .args(["-Qu"])
-                .output()
-                .map(|output| {
-                    String::from_utf8_lossy(&output.stdout)
-                        .lines()
-                        .map(String::from)
-                        .collect()
-                })
-                .unwrap_or_default()
-        },
-        PackageManager::Zypper => {
-            Command::new("zypper")
-                .args(["list-updates"])
-                .output()
-                .map(|output| {
-                    String::from_utf8_lossy(&output.stdout)
-                        .lines()
-                        .filter(|line| !line.starts_with("S") && !line.is_empty())
-                        .map(String::from)
-                        .collect()
-                })
-                .unwrap_or_default()
-        },
-        PackageManager::Unknown => Vec::new(),
+// Basit glob eşleştirme: '*' ve '?' desteklenir
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let t: Vec<char> = text.chars().collect();
+    let (mut pi, mut ti) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+
+    while ti < t.len() {
+        if pi < p.len() && (p[pi] == '?' || p[pi] == t[ti]) {
+            pi += 1;
+            ti += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            star = Some((pi, ti));
+            pi += 1;
+        } else if let Some((star_pi, star_ti)) = star {
+            pi = star_pi + 1;
+            ti = star_ti + 1;
+            star = Some((star_pi, star_ti + 1));
+        } else {
+            return false;
+        }
     }
+
+    while pi < p.len() && p[pi] == '*' {
+        pi += 1;
+    }
+    pi == p.len()
 }
 
-fn get_service_status(service: &str) -> (bool, bool) {
+fn get_service_status(service: &str, aliases: &BTreeMap<String, Vec<String>>) -> (bool, bool) {
     // Try different service names for each service
-    let service_names = match service {
-        "mysql" => vec!["mysql", "mariadb", "mysqld"],
-        "redis" => vec!["redis", "redis-server"],
-        "nginx" => vec!["nginx", "nginx-main"],
-        _ => vec![service],
-    };
+    let mut service_names = vec![service];
+    if let Some(alternatives) = aliases.get(service.trim_end_matches(".service")) {
+        service_names.extend(alternatives.iter().map(String::as_str));
+    }
 
     for service_name in service_names {
         // systemd kullanılıyorsa
@@ -298,7 +316,77 @@ fn get_service_status(service: &str) -> (bool, bool) {
     (false, false)
 }
 
-fn get_services() -> Vec<ServiceInfo> {
+// Desen servis adıyla ya da takma adlarından biriyle eşleşiyor mu ("nginx", "nginx.service", "php*-fpm")
+fn service_matches(pattern: &str, service: &str, aliases: &BTreeMap<String, Vec<String>>) -> bool {
+    let short_name = service.trim_end_matches(".service");
+    let mut patterns = vec![pattern];
+    if let Some(alternatives) = aliases.get(pattern.trim_end_matches(".service")) {
+        patterns.extend(alternatives.iter().map(String::as_str));
+    }
+    patterns
+        .iter()
+        .any(|p| glob_match(p, service) || glob_match(p, short_name))
+}
+
+// Kritik servisler izleme listesinde olmasa da toplanır; hariç tutma her durumda geçerlidir
+fn service_selected(service: &str, config: &config::ServicesConfig) -> bool {
+    let included = config.monitored_services.is_empty()
+        || config
+            .monitored_services
+            .iter()
+            .chain(config.critical_services.iter())
+            .any(|p| service_matches(p, service, &config.aliases));
+    included && !service_excluded(service, config)
+}
+
+fn service_excluded(service: &str, config: &config::ServicesConfig) -> bool {
+    config.excluded_services.iter().any(|p| service_matches(p, service, &config.aliases))
+}
+
+fn get_services(config: &config::ServicesConfig) -> Vec<ServiceInfo> {
+    let pkg_manager = packages::detect_package_manager();
+
+    // systemd varsa servis durumları paylaşılan D-Bus bağlantısından tek sorguyla alınır
+    let mut services = match systemd::list_services(|name| service_selected(name, config)) {
+        Ok(mut services) => {
+            for service in services.iter_mut() {
+                service.version = version::get_service_version(
+                    &service.name,
+                    service.exec_path.as_deref(),
+                    pkg_manager,
+                    config,
+                );
+            }
+            systemd::detect_flapping(
+                &mut services,
+                Duration::from_secs(config.flap_window_minutes * 60),
+                config.flap_threshold,
+            );
+            services
+        }
+        Err(e) => {
+            debug!("systemd D-Bus sorgusu başarısız, komut satırı araçlarına dönülüyor: {}", e);
+            get_legacy_services(config, pkg_manager)
+        }
+    };
+
+    for service in services.iter_mut() {
+        service.critical = config
+            .critical_services
+            .iter()
+            .any(|p| service_matches(p, &service.name, &config.aliases));
+
+        // Kaynak kullanımı systemd'nin bildirdiği cgroup dizininden okunur
+        service.resources = service
+            .control_group
+            .as_deref()
+            .and_then(cgroup::get_service_resources);
+    }
+
+    services
+}
+
+fn get_legacy_services(config: &config::ServicesConfig, pkg_manager: packages::PackageManager) -> Vec<ServiceInfo> {
     let mut services = Vec::new();
     
     // systemd kullanılıyorsa
@@ -309,15 +397,16 @@ fn get_services() -> Vec<ServiceInfo> {
             if let Ok(output_str) = String::from_utf8(output.stdout) {
                 for line in output_str.lines() {
                     if let Some(service_name) = line.split_whitespace().next() {
-                        if !service_name.is_empty() && !service_name.contains("@") {
-                            let (active, enabled) = get_service_status(service_name);
-                            let version = get_service_version(service_name);
+                        if !service_name.is_empty() && !service_name.contains("@") && service_selected(service_name, config) {
+                            let (active, enabled) = get_service_status(service_name, &config.aliases);
+                            let version = version::get_service_version(service_name, None, pkg_manager, config);
                             
                             services.push(ServiceInfo {
                                 name: service_name.to_string(),
                                 active,
                                 enabled,
                                 version,
+                                ..Default::default()
                             });
                         }
                     }
@@ -333,15 +422,16 @@ fn get_services() -> Vec<ServiceInfo> {
             if let Ok(output_str) = String::from_utf8(output.stdout) {
                 for service_name in output_str.lines() {
                     let service_name = service_name.trim();
-                    if !service_name.is_empty() {
-                        let (active, enabled) = get_service_status(service_name);
-                        let version = get_service_version(service_name);
+                    if !service_name.is_empty() && service_selected(service_name, config) {
+                        let (active, enabled) = get_service_status(service_name, &config.aliases);
+                        let version = version::get_service_version(service_name, None, pkg_manager, config);
                         
                         services.push(ServiceInfo {
                             name: service_name.to_string(),
                             active,
                             enabled,
                             version,
+                            ..Default::default()
                         });
                     }
                 }
@@ -356,15 +446,16 @@ fn get_services() -> Vec<ServiceInfo> {
             if let Ok(output_str) = String::from_utf8(output.stdout) {
                 for service_name in output_str.lines() {
                     let service_name = service_name.trim();
-                    if !service_name.is_empty() {
-                        let (active, enabled) = get_service_status(service_name);
-                        let version = get_service_version(service_name);
+                    if !service_name.is_empty() && service_selected(service_name, config) {
+                        let (active, enabled) = get_service_status(service_name, &config.aliases);
+                        let version = version::get_service_version(service_name, None, pkg_manager, config);
                         
                         services.push(ServiceInfo {
                             name: service_name.to_string(),
                             active,
                             enabled,
                             version,
+                            ..Default::default()
                         });
                     }
                 }
@@ -375,40 +466,7 @@ fn get_services() -> Vec<ServiceInfo> {
     services
 }
 
-fn get_service_version(service: &str) -> Option<String> {
-    // Önce servis adından komut adını çıkar
-    let cmd_name = service.split('.').next().unwrap_or(service);
-    
-    // Versiyon komutlarını dene
-    let version_commands = vec![
-        (cmd_name.to_string(), vec!["--version"]),
-        (cmd_name.to_string(), vec!["-v"]),
-        (cmd_name.to_string(), vec!["-V"]),
-        (format!("{}-server", cmd_name).to_string(), vec!["--version"]),
-        (format!("{}-cli", cmd_name).to_string(), vec!["--version"]),
-    ];
-
-    for (cmd, args) in version_commands {
-        if let Ok(output) = Command::new(&cmd).args(&args).output() {
-            // Önce stdout'u kontrol et
-            if let Ok(version) = String::from_utf8(output.stdout) {
-                if !version.is_empty() {
-                    return version.lines().next().map(String::from);
-                }
-            }
-            // Sonra stderr'i kontrol et
-            if let Ok(version) = String::from_utf8(output.stderr) {
-                if !version.is_empty() {
-                    return version.lines().next().map(String::from);
-                }
-            }
-        }
-    }
-
-    None
-}
-
-fn get_security_info() -> SecurityInfo {
+fn get_security_info(config: &config::MonitorConfig) -> SecurityInfo {
     let firewall_enabled = Command::new("ufw")
         .arg("status")
         .output()
@@ -443,13 +501,23 @@ fn get_security_info() -> SecurityInfo {
         })
         .unwrap_or_default();
 
-    let package_updates = get_package_updates();
+    let pkg_manager = packages::detect_package_manager();
+    let package_index = packages::get_package_index_info(pkg_manager);
+    let package_updates = packages::get_package_updates(pkg_manager, &config.security.packages);
+    let security_update_count = package_updates.iter().filter(|u| u.is_security).count();
+
+    let ssh = ssh::get_ssh_info(&config.security.ssh);
+    let kernel = kernel::get_kernel_security_info(&config.security.kernel);
 
     SecurityInfo {
         firewall_enabled,
         fail2ban_active,
         open_ports,
+        package_index,
         package_updates,
+        security_update_count,
+        ssh,
+        kernel,
     }
 }
 
@@ -473,16 +541,12 @@ fn get_memory_info(sys: &System) -> MemoryInfo {
 }
 
 fn get_load_average() -> LoadAverage {
-    let output = Command::new("uptime")
-        .output()
-        .expect("Failed to execute uptime command");
-    let output_str = String::from_utf8_lossy(&output.stdout);
-    let load_avg: Vec<f64> = output_str
-        .split("load average:")
-        .nth(1)
-        .unwrap_or("")
-        .split(',')
-        .map(|s| s.trim().parse().unwrap_or(0.0))
+    // /proc/loadavg: "0.52 0.58 0.59 1/467 12345"
+    let load_avg: Vec<f64> = fs::read_to_string("/proc/loadavg")
+        .unwrap_or_default()
+        .split_whitespace()
+        .take(3)
+        .map(|s| s.parse().unwrap_or(0.0))
         .collect();
     
     LoadAverage {
@@ -614,7 +678,7 @@ fn get_user_access() -> UserAccess {
     }
 }
 
-fn get_hardware_info() -> HardwareInfo {
+fn get_hardware_info(config: &config::HardwareConfig) -> HardwareInfo {
     let cpu_info = Command::new("lscpu")
         .output()
         .map(|output| {
@@ -650,144 +714,76 @@ fn get_hardware_info() -> HardwareInfo {
         })
         .unwrap_or(0);
 
-    let disk_info = Command::new("lsblk")
-        .output()
-        .map(|output| {
-            String::from_utf8_lossy(&output.stdout)
-                .lines()
-                .map(String::from)
-                .collect()
-        })
-        .unwrap_or_default();
-
-    let system_info = Command::new("dmidecode")
-        .output()
-        .map(|output| {
-            let output_str = String::from_utf8_lossy(&output.stdout);
-            let vendor = output_str
-                .lines()
-                .find(|line| line.contains("Vendor:"))
-                .and_then(|line| line.split(':').nth(1))
-                .map(|s| s.trim().to_string())
-                .unwrap_or_default();
-            let model = output_str
-                .lines()
-                .find(|line| line.contains("Product Name:"))
-                .and_then(|line| line.split(':').nth(1))
-                .map(|s| s.trim().to_string())
-                .unwrap_or_default();
-            (vendor, model)
-        })
-        .unwrap_or_default();
+    let system = hardware::get_system_dmi().clone();
+    let block_devices = hardware::get_block_devices(&config.smart);
+    let disk_health = smart::get_disk_health(&block_devices, &config.smart);
 
     HardwareInfo {
         cpu_model: cpu_info,
         cores,
         total_ram_mb: total_ram,
-        disk_info,
-        system_vendor: system_info.0,
-        system_model: system_info.1,
+        system_vendor: system.manufacturer.clone().unwrap_or_default(),
+        system_model: system.product.clone().unwrap_or_default(),
+        system,
+        memory_modules: hardware::get_memory_modules().to_vec(),
+        block_devices,
+        disk_health,
+        virtualization: virt::detect().clone(),
     }
 }
 
-fn get_uptime_info() -> UptimeInfo {
-    // Mevcut uptime bilgisini al
-    let uptime_output = Command::new("uptime")
-        .output()
-        .expect("uptime komutu çalıştırılamadı");
-    let uptime_str = String::from_utf8_lossy(&uptime_output.stdout);
-    
-    // Son boot zamanını al
-    let boot_time = Command::new("who")
-        .args(["-b"])
-        .output()
-        .expect("who komutu çalıştırılamadı");
-    let boot_str = String::from_utf8_lossy(&boot_time.stdout);
-    
+fn get_uptime_info(config: &config::RebootConfig) -> UptimeInfo {
+    // /proc/uptime: "350735.47 234388.90" (çalışma süresi, boşta geçen süre)
+    let uptime_seconds = fs::read_to_string("/proc/uptime")
+        .ok()
+        .and_then(|content| content.split_whitespace().next().and_then(|v| v.parse::<f64>().ok()))
+        .map(|seconds| seconds as u64);
+
+    // Son boot zamanı
+    let last_boot_time = boot_time()
+        .and_then(|btime| chrono::DateTime::from_timestamp(btime, 0))
+        .map(|dt| dt.with_timezone(&chrono::Local).to_rfc3339());
+
     // Reboot geçmişini al
-    let reboot_history = get_reboot_history();
+    let reboot_history = reboot::get_boot_history();
+    let last_shutdown = reboot_history
+        .iter()
+        .find(|boot| boot.shutdown != reboot::ShutdownKind::Running)
+        .map(|boot| boot.shutdown);
+
+    // Bekleyen yeniden başlatma durumunu kontrol et
+    let reboot_status = reboot::get_reboot_status(config);
 
     UptimeInfo {
-        current_uptime: uptime_str.trim().to_string(),
-        last_boot_time: boot_str.trim().to_string(),
+        uptime_seconds,
+        current_uptime: uptime_seconds.map(format_duration).unwrap_or_default(),
+        last_boot_time,
         reboot_history,
+        last_shutdown,
+        reboot_required: reboot_status.required,
+        reboot_reasons: reboot_status.reasons,
+        deleted_library_processes: reboot_status.deleted_library_processes,
     }
 }
 
-fn get_reboot_history() -> Vec<RebootRecord> {
-    let mut history = Vec::new();
-    
-    // last reboot komutundan reboot geçmişini al
-    if let Ok(output) = Command::new("last")
-        .arg("reboot")
-        .output() {
-        let output_str = String::from_utf8_lossy(&output.stdout);
-        
-        for line in output_str.lines() {
-            if line.contains("reboot") {
-                let parts: Vec<&str> = line.split_whitespace().collect();
-                if parts.len() >= 4 {
-                    let timestamp = format!("{} {} {}", parts[3], parts[4], parts[5]);
-                    let reason = if parts.len() > 6 {
-                        Some(parts[6..].join(" "))
-                    } else {
-                        None
-                    };
-                    
-                    history.push(RebootRecord {
-                        timestamp,
-                        reason,
-                    });
-                }
-            }
-        }
-    }
-    
-    // journalctl'den de reboot geçmişini al (systemd sistemlerde)
-    if Command::new("which").arg("journalctl").output().map(|o| o.status.success()).unwrap_or(false) {
-        if let Ok(output) = Command::new("journalctl")
-            .args(["--list-boots", "--no-pager"])
-            .output() {
-            let output_str = String::from_utf8_lossy(&output.stdout);
-            
-            for line in output_str.lines().skip(1) { // İlk satırı atla (başlık)
-                let parts: Vec<&str> = line.split_whitespace().collect();
-                if parts.len() >= 3 {
-                    let timestamp = format!("{} {}", parts[1], parts[2]);
-                    
-                    // Bu boot için kapanış nedenini bul
-                    let reason = if let Ok(reason_output) = Command::new("journalctl")
-                        .args(["-b", parts[0], "--no-pager"])
-                        .output() {
-                        let reason_str = String::from_utf8_lossy(&reason_output.stdout);
-                        if let Some(last_line) = reason_str.lines().last() {
-                            if last_line.contains("shutdown") || last_line.contains("reboot") {
-                                Some(last_line.to_string())
-                            } else {
-                                None
-                            }
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    };
-                    
-                    history.push(RebootRecord {
-                        timestamp,
-                        reason,
-                    });
-                }
-            }
-        }
+// Disk, ağ arayüzü ve kullanıcı listeleri nadiren değişir, her döngüde yeniden taranmaz
+const LIST_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+// CPU kullanımı önceki örnekle farktan hesaplanır, bu yüzden System döngüler arasında korunur
+fn refresh_system(sys: &mut System, refresh_lists: bool) {
+    if refresh_lists {
+        sys.refresh_disks_list();
+        sys.refresh_networks_list();
+        sys.refresh_users_list();
     }
-    
-    history
+    sys.refresh_cpu();
+    sys.refresh_memory();
+    sys.refresh_disks();
+    sys.refresh_networks();
+    sys.refresh_processes();
 }
 
-fn get_system_info() -> SystemInfo {
-    let mut sys = System::new_all();
-    sys.refresh_all();
+fn get_system_info(sys: &System, config: &config::MonitorConfig) -> SystemInfo {
 
     // Hostname
     let hostname = sys.host_name().unwrap_or_else(|| "Unknown".to_string());
@@ -799,59 +795,44 @@ fn get_system_info() -> SystemInfo {
     let os_version = sys.long_os_version().unwrap_or_else(|| "Unknown".to_string());
 
     // CPU Info
-    let cpu_info = CpuInfo {
-        usage_percent: sys.global_cpu_info().cpu_usage(),
-        temperature_celsius: None, // Sıcaklık bilgisi için ek kütüphane gerekebilir
-        frequency_mhz: sys.global_cpu_info().frequency() as f32,
-    };
+    let cpu_info = get_cpu_info(sys);
 
     // Memory Info
-    let memory_info = MemoryInfo {
-        total_mb: sys.total_memory() / 1024 / 1024,
-        used_mb: sys.used_memory() / 1024 / 1024,
-        free_mb: (sys.total_memory() - sys.used_memory()) / 1024 / 1024,
-    };
+    let memory_info = get_memory_info(sys);
 
     // Disk Info
-    let disks = get_disk_info(&sys);
+    let disks = get_disk_info(sys);
 
     // Network Info
-    let network_info = get_network_info(&sys);
+    let network_info = get_network_info(sys);
 
     // User Access
     let user_access = get_user_access();
 
     // Services
-    let services = get_services();
+    let services = get_services(&config.services);
 
     // Security Info
-    let security_info = get_security_info();
+    let security_info = get_security_info(config);
 
     // Hardware Info
-    let hardware_info = get_hardware_info();
+    let hardware_info = get_hardware_info(&config.hardware);
 
     // Uptime Info
-    let uptime_info = get_uptime_info();
+    let uptime_info = get_uptime_info(&config.reboot);
+
+    // Package Inventory
+    let package_inventory = inventory::get_inventory_info(packages::detect_package_manager(), &config.inventory);
 
     // Process List
-    let mut process_list = Vec::new();
-    for (pid, process) in sys.processes() {
-        process_list.push(ProcessInfo {
-            pid: *pid,
-            name: process.name().to_string(),
-            cpu_usage: process.cpu_usage(),
-            memory_usage: process.memory(),
-            status: process.status().to_string(),
-            user: process.user_id().unwrap_or(0).to_string(),
-            command: process.cmd().join(" "),
-        });
-    }
+    let process_summary = process::get_process_summary(sys, &config.processes);
 
-    SystemInfo {
+    let mut system_info = SystemInfo {
         cpu: cpu_info,
         memory: memory_info,
         load_avg: get_load_average(),
         disks,
+        storage: storage::get_storage_health(&config.storage),
         network: network_info,
         user_access,
         services,
@@ -861,9 +842,28 @@ fn get_system_info() -> SystemInfo {
         hostname,
         kernel_version,
         os_version,
-        process_list,
+        timezone: timesync::get_timezone(),
+        time_sync: timesync::get_time_sync_info(),
+        certificates: certs::get_certificates(&config.certificates),
+        log_watches: logwatch::get_log_watches(&config.logs),
+        process_count: process_summary.total,
+        process_list: process_summary.processes,
+        process_tree: process_summary.tree,
+        process_findings: process_summary.findings,
+        zombie_count: process_summary.zombie_count,
+        uninterruptible_count: process_summary.uninterruptible_count,
+        stuck_processes: process_summary.stuck_processes,
+        oom_kills: oom::get_oom_kills(),
+        containers: containers::get_containers(&config.containers),
+        package_inventory,
+        alerts: Vec::new(),
         timestamp: chrono::Local::now().to_rfc3339(),
-    }
+    };
+
+    // Toplanan verilere göre uyarıları değerlendir
+    system_info.alerts = alerts::evaluate_alerts(&system_info, config);
+
+    system_info
 }
 
 fn save_to_json(info: &SystemInfo) -> io::Result<()> {
@@ -893,6 +893,18 @@ fn run_monitor() -> Result<()> {
         }
     };
 
+    // İzleme yapılandırmasını yükle
+    let monitor_config = match config::MonitorConfig::load() {
+        Ok(config) => {
+            info!("İzleme yapılandırması yüklendi");
+            config
+        }
+        Err(e) => {
+            error!("İzleme yapılandırması yüklenemedi: {}", e);
+            return Err(e);
+        }
+    };
+
     let api_client = match api::ApiClient::new(api_config) {
         Ok(client) => {
             info!("API istemcisi oluşturuldu");
@@ -905,9 +917,25 @@ fn run_monitor() -> Result<()> {
     };
 
     info!("Sistem izleme başlatıldı");
+
+    // İlk örnek: sonraki yenilemede CPU kullanımı gerçek farktan hesaplanabilsin
+    let mut sys = System::new();
+    refresh_system(&mut sys, true);
+    let mut last_list_refresh = Instant::now();
+    thread::sleep(System::MINIMUM_CPU_UPDATE_INTERVAL);
     
     loop {
-        let system_info = get_system_info();
+        let refresh_lists = last_list_refresh.elapsed() >= LIST_REFRESH_INTERVAL;
+        if refresh_lists {
+            last_list_refresh = Instant::now();
+        }
+        refresh_system(&mut sys, refresh_lists);
+
+        let system_info = get_system_info(&sys, &monitor_config);
+
+        for alert in &system_info.alerts {
+            warn!("[{}] {}", alert.source, alert.message);
+        }
         
         // JSON dosyasına kaydet
         if let Err(e) = save_to_json(&system_info) {
@@ -941,7 +969,9 @@ fn main() -> Result<()> {
         .working_directory(".")
         .user("nobody")
         .group("nobody")
-        .umask(0o027);
+        .umask(0o027)
+        // /dev/kmsg dmesg_restrict nedeniyle yalnızca root iken açılabilir
+        .privileged_action(oom::open_kmsg);
 
     match daemonize.start() {
         Ok(_) => {
@@ -0,0 +1,226 @@
+use log::warn;
+use nix::fcntl::OFlag;
+use regex::Regex;
+use serde::Serialize;
+use std::fs::{File, OpenOptions};
+use std::io::{ErrorKind, Read};
+use std::os::unix::fs::OpenOptionsExt;
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+// /dev/kmsg açık tutulur, böylece her döngüde yalnızca yeni kayıtlar okunur
+static KMSG: Mutex<Option<File>> = Mutex::new(None);
+// Açılamadıysa her döngüde yeniden denenmez, doğrudan journal'a geçilir
+static KMSG_UNAVAILABLE: AtomicBool = AtomicBool::new(false);
+// kmsg okunamazsa journal'da kalınan yer
+static JOURNAL_CURSOR: Mutex<Option<String>> = Mutex::new(None);
+static KILLED_PROCESS: OnceLock<Regex> = OnceLock::new();
+
+#[derive(Debug, Serialize)]
+pub struct OomKill {
+    pub timestamp: Option<String>,
+    pub pid: u32,
+    pub process: String,
+    pub total_vm_kb: Option<u64>,
+    pub anon_rss_kb: Option<u64>,
+    pub cgroup_oom: bool,
+    pub source: String,
+}
+
+// Son çağrıdan bu yana gerçekleşen OOM killer olaylarını döner
+pub fn get_oom_kills() -> Vec<OomKill> {
+    match read_kmsg() {
+        Some(kills) => kills,
+        None => read_journal(),
+    }
+}
+
+// Daemon yetkileri bırakılmadan önce çağrılır: dmesg_restrict etkinse /dev/kmsg yalnızca
+// root tarafından açılabilir, izin açılışta denetlendiği için tanımlayıcı nobody olarak da okunur
+pub fn open_kmsg() {
+    let mut kmsg = KMSG.lock().unwrap_or_else(|e| e.into_inner());
+    if kmsg.is_none() {
+        *kmsg = try_open_kmsg();
+    }
+}
+
+fn try_open_kmsg() -> Option<File> {
+    match OpenOptions::new()
+        .read(true)
+        .custom_flags(OFlag::O_NONBLOCK.bits())
+        .open("/dev/kmsg")
+    {
+        Ok(file) => Some(file),
+        Err(e) => {
+            if !KMSG_UNAVAILABLE.swap(true, Ordering::Relaxed) {
+                warn!("/dev/kmsg açılamadı ({}), OOM olayları journal'dan okunacak", e);
+            }
+            None
+        }
+    }
+}
+
+fn read_kmsg() -> Option<Vec<OomKill>> {
+    let mut kmsg = KMSG.lock().unwrap_or_else(|e| e.into_inner());
+    if kmsg.is_none() && !KMSG_UNAVAILABLE.load(Ordering::Relaxed) {
+        *kmsg = try_open_kmsg();
+    }
+    let file = kmsg.as_mut()?;
+    let boot_time = crate::boot_time();
+
+    let mut kills = Vec::new();
+    let mut buf = vec![0u8; 8192];
+    loop {
+        // Her read çağrısı tek bir kayıt döner: "6,1234,5678901,-;mesaj"
+        match file.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => {
+                if let Some(kill) = parse_kmsg_record(&String::from_utf8_lossy(&buf[..n]), boot_time) {
+                    kills.push(kill);
+                }
+            }
+            Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+            // Halka tampon biz okumadan üzerine yazıldıysa EPIPE döner, okumaya devam edilir
+            Err(e) if e.raw_os_error() == Some(nix::errno::Errno::EPIPE as i32) => continue,
+            Err(_) => {
+                *kmsg = None;
+                return None;
+            }
+        }
+    }
+    Some(kills)
+}
+
+fn read_journal() -> Vec<OomKill> {
+    let mut cursor = JOURNAL_CURSOR.lock().unwrap_or_else(|e| e.into_inner());
+
+    let mut command = Command::new("journalctl");
+    command.args(["-k", "-q", "--no-pager", "-o", "short-unix", "--show-cursor"]);
+    match cursor.as_deref() {
+        Some(c) => command.arg(format!("--after-cursor={}", c)),
+        None => command.arg("-b"),
+    };
+    let Ok(output) = command.output() else {
+        return Vec::new();
+    };
+    if !output.status.success() {
+        return Vec::new();
+    }
+
+    let mut kills = Vec::new();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        if let Some(c) = line.strip_prefix("-- cursor: ") {
+            *cursor = Some(c.to_string());
+            continue;
+        }
+        if let Some(kill) = parse_journal_line(line) {
+            kills.push(kill);
+        }
+    }
+    kills
+}
+
+// Her kayıt "6,1234,5678901,-;mesaj" biçimindedir; üçüncü alan açılıştan beri mikrosaniye
+fn parse_kmsg_record(record: &str, boot_time: Option<i64>) -> Option<OomKill> {
+    let (header, message) = record.split_once(';')?;
+    let timestamp = header
+        .split(',')
+        .nth(2)
+        .and_then(|usec| usec.parse::<i64>().ok())
+        .zip(boot_time)
+        .and_then(|(usec, boot)| chrono::DateTime::from_timestamp_micros(boot * 1_000_000 + usec))
+        .map(|dt| dt.with_timezone(&chrono::Local).to_rfc3339());
+    parse_oom_line(message.lines().next().unwrap_or(""), timestamp, "kmsg")
+}
+
+// "1697712345.123456 host kernel: Out of memory: Killed process ..."
+fn parse_journal_line(line: &str) -> Option<OomKill> {
+    let timestamp = line
+        .split_whitespace()
+        .next()
+        .and_then(|ts| ts.parse::<f64>().ok())
+        .and_then(|ts| chrono::DateTime::from_timestamp_micros((ts * 1_000_000.0) as i64))
+        .map(|dt| dt.with_timezone(&chrono::Local).to_rfc3339());
+    parse_oom_line(line, timestamp, "journal")
+}
+
+// "Out of memory: Killed process 1234 (java) total-vm:123kB, anon-rss:45kB, ..."
+// "Memory cgroup out of memory: Killed process 1234 (java) ..."
+fn parse_oom_line(line: &str, timestamp: Option<String>, source: &str) -> Option<OomKill> {
+    let regex = KILLED_PROCESS.get_or_init(|| {
+        Regex::new(r"Killed process (\d+) \(([^)]*)\)(?:.*?total-vm:(\d+)kB)?(?:.*?anon-rss:(\d+)kB)?").unwrap()
+    });
+    let caps = regex.captures(line)?;
+    Some(OomKill {
+        timestamp,
+        pid: caps[1].parse().ok()?,
+        process: caps[2].to_string(),
+        total_vm_kb: caps.get(3).and_then(|m| m.as_str().parse().ok()),
+        anon_rss_kb: caps.get(4).and_then(|m| m.as_str().parse().ok()),
+        cgroup_oom: line.contains("Memory cgroup out of memory"),
+        source: source.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_kmsg_records() {
+        let boot_time = Some(1_697_700_000);
+        let kills: Vec<OomKill> = include_str!("../tests/fixtures/oom/kmsg.txt")
+            .lines()
+            .filter_map(|record| parse_kmsg_record(record, boot_time))
+            .collect();
+        assert_eq!(kills.len(), 2);
+
+        assert_eq!(kills[0].pid, 48213);
+        assert_eq!(kills[0].process, "java");
+        assert_eq!(kills[0].total_vm_kb, Some(8_123_456));
+        assert_eq!(kills[0].anon_rss_kb, Some(3_987_654));
+        assert!(!kills[0].cgroup_oom);
+        assert_eq!(kills[0].source, "kmsg");
+        let expected = chrono::DateTime::from_timestamp(1_697_700_000 + 12_345, 678_901_000)
+            .unwrap()
+            .with_timezone(&chrono::Local)
+            .to_rfc3339();
+        assert_eq!(kills[0].timestamp.as_deref(), Some(expected.as_str()));
+
+        assert_eq!(kills[1].pid, 51002);
+        assert_eq!(kills[1].process, "php-fpm: pool www");
+        assert!(kills[1].cgroup_oom);
+
+        // Açılış zamanı bilinmiyorsa olay yine raporlanır
+        let record = include_str!("../tests/fixtures/oom/kmsg.txt").lines().nth(3).unwrap();
+        let kill = parse_kmsg_record(record, None).unwrap();
+        assert_eq!(kill.timestamp, None);
+    }
+
+    #[test]
+    fn parses_journal_lines() {
+        let kills: Vec<OomKill> = include_str!("../tests/fixtures/oom/journal.txt")
+            .lines()
+            .filter_map(parse_journal_line)
+            .collect();
+        assert_eq!(kills.len(), 2);
+
+        assert_eq!(kills[0].pid, 2210);
+        assert_eq!(kills[0].process, "mysqld");
+        assert_eq!(kills[0].total_vm_kb, Some(2_456_780));
+        assert_eq!(kills[0].anon_rss_kb, Some(1_203_400));
+        assert_eq!(kills[0].source, "journal");
+        let expected = chrono::DateTime::from_timestamp(1_697_712_345, 123_456_000)
+            .unwrap()
+            .with_timezone(&chrono::Local)
+            .to_rfc3339();
+        assert_eq!(kills[0].timestamp.as_deref(), Some(expected.as_str()));
+
+        // Eski çekirdekler total-vm/anon-rss yazmaz
+        assert_eq!(kills[1].pid, 3301);
+        assert_eq!(kills[1].process, "node");
+        assert_eq!(kills[1].total_vm_kb, None);
+        assert!(kills[1].cgroup_oom);
+    }
+}
@@ -0,0 +1,525 @@
+use crate::config::PackageConfig;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::process::Command;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant, SystemTime};
+
+// Güncelleme listesi, indeks veya kurulu paketler değişene ya da aralık dolana kadar tekrar kullanılır
+static UPDATES_CACHE: Mutex<Option<(UpdatesKey, Instant, Vec<PackageUpdate>)>> = Mutex::new(None);
+
+// (indeksin, paket veritabanının) değiştirilme zamanı
+type UpdatesKey = (Option<SystemTime>, Option<SystemTime>);
+
+// Paket yöneticisi çalışma sırasında değişmez, bir kez tespit edilir
+static DETECTED_MANAGER: OnceLock<PackageManager> = OnceLock::new();
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PackageUpdate {
+    pub name: String,
+    pub current_version: Option<String>,
+    pub new_version: String,
+    pub repository: Option<String>,
+    pub is_security: bool,
+    pub severity: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct PackageIndexInfo {
+    pub manager: PackageManager,
+    // İndeks daemon tarafından yenilenmez (root gerekir); apt-daily, dnf-makecache gibi zamanlayıcılara bırakılır
+    pub cache_age_seconds: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PackageManager {
+    Apt,
+    Yum,
+    Dnf,
+    Pacman,
+    Zypper,
+    #[default]
+    Unknown,
+}
+
+pub fn detect_package_manager() -> PackageManager {
+    *DETECTED_MANAGER.get_or_init(probe_package_manager)
+}
+
+fn probe_package_manager() -> PackageManager {
+    if Command::new("which").arg("apt").output().map(|o| o.status.success()).unwrap_or(false) {
+        PackageManager::Apt
+    } else if Command::new("which").arg("yum").output().map(|o| o.status.success()).unwrap_or(false) {
+        PackageManager::Yum
+    } else if Command::new("which").arg("dnf").output().map(|o| o.status.success()).unwrap_or(false) {
+        PackageManager::Dnf
+    } else if Command::new("which").arg("pacman").output().map(|o| o.status.success()).unwrap_or(false) {
+        PackageManager::Pacman
+    } else if Command::new("which").arg("zypper").output().map(|o| o.status.success()).unwrap_or(false) {
+        PackageManager::Zypper
+    } else {
+        PackageManager::Unknown
+    }
+}
+
+fn command_stdout(cmd: &str, args: &[&str]) -> Option<String> {
+    Command::new(cmd)
+        .args(args)
+        .output()
+        .ok()
+        .map(|output| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+// Paket indeksinin son güncellenme zamanını gösteren dosya/dizinler
+fn cache_paths(pkg_manager: PackageManager) -> &'static [&'static str] {
+    match pkg_manager {
+        PackageManager::Apt => &["/var/lib/apt/periodic/update-success-stamp", "/var/lib/apt/lists"],
+        PackageManager::Yum => &["/var/cache/yum"],
+        PackageManager::Dnf => &["/var/cache/dnf/last_makecache", "/var/cache/dnf"],
+        PackageManager::Pacman => &["/var/lib/pacman/sync"],
+        PackageManager::Zypper => &["/var/cache/zypp/raw"],
+        PackageManager::Unknown => &[],
+    }
+}
+
+// Kurulu paket veritabanı; paket kurulup kaldırıldığında değişir
+fn database_paths(pkg_manager: PackageManager) -> &'static [&'static str] {
+    match pkg_manager {
+        PackageManager::Apt => &["/var/lib/dpkg/status"],
+        PackageManager::Yum | PackageManager::Dnf | PackageManager::Zypper => &[
+            "/var/lib/rpm/rpmdb.sqlite",
+            "/var/lib/rpm/Packages",
+            "/usr/lib/sysimage/rpm/rpmdb.sqlite",
+            "/usr/lib/sysimage/rpm/Packages",
+        ],
+        PackageManager::Pacman => &["/var/lib/pacman/local"],
+        PackageManager::Unknown => &[],
+    }
+}
+
+fn modified(paths: &[&str]) -> Option<SystemTime> {
+    paths
+        .iter()
+        .find_map(|path| fs::metadata(path).and_then(|m| m.modified()).ok())
+}
+
+fn cache_age(pkg_manager: PackageManager) -> Option<Duration> {
+    modified(cache_paths(pkg_manager)).and_then(|modified| SystemTime::now().duration_since(modified).ok())
+}
+
+pub fn get_package_index_info(pkg_manager: PackageManager) -> PackageIndexInfo {
+    PackageIndexInfo {
+        manager: pkg_manager,
+        cache_age_seconds: cache_age(pkg_manager).map(|age| age.as_secs()),
+    }
+}
+
+pub fn get_package_updates(pkg_manager: PackageManager, config: &PackageConfig) -> Vec<PackageUpdate> {
+    let key = (modified(cache_paths(pkg_manager)), modified(database_paths(pkg_manager)));
+    let interval = Duration::from_secs(config.updates_interval_hours * 3600);
+
+    let mut cache = UPDATES_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some((cached_key, at, updates)) = cache.as_ref() {
+        if *cached_key == key && at.elapsed() < interval {
+            return updates.clone();
+        }
+    }
+
+    let updates = list_package_updates(pkg_manager);
+    *cache = Some((key, Instant::now(), updates.clone()));
+    updates
+}
+
+// Güncellemeler sadece yerel önbellekten okunur, sistem durumu değiştirilmez
+fn list_package_updates(pkg_manager: PackageManager) -> Vec<PackageUpdate> {
+    match pkg_manager {
+        PackageManager::Apt => {
+            command_stdout("apt", &["list", "--upgradable"])
+                .map(|out| parse_apt_upgradable(&out))
+                .unwrap_or_default()
+        },
+        PackageManager::Yum | PackageManager::Dnf => {
+            let cmd = if matches!(pkg_manager, PackageManager::Yum) { "yum" } else { "dnf" };
+            let mut updates = command_stdout(cmd, &["--cacheonly", "check-update"])
+                .map(|out| parse_yum_check_update(&out))
+                .unwrap_or_default();
+
+            // Güvenlik bildirimlerini updateinfo üzerinden eşleştir
+            if let Some(out) = command_stdout(cmd, &["--cacheonly", "updateinfo", "list", "--security"]) {
+                mark_security(&mut updates, &parse_yum_updateinfo(&out), |nevra, name| nevra_matches(nevra, name));
+            }
+            updates
+        },
+        PackageManager::Pacman => {
+            let mut updates = command_stdout("pacman", &["-Qu"])
+                .map(|out| parse_pacman_updates(&out))
+                .unwrap_or_default();
+
+            // arch-audit kuruluysa güvenlik açığı olan paketleri işaretle
+            if let Some(out) = command_stdout("arch-audit", &["--upgradable", "--format", "%n|%s"]) {
+                mark_security(&mut updates, &parse_arch_audit(&out), |affected, name| affected == name);
+            }
+            updates
+        },
+        PackageManager::Zypper => {
+            let mut updates = command_stdout("zypper", &["--non-interactive", "--no-refresh", "list-updates"])
+                .map(|out| parse_zypper_list_updates(&out))
+                .unwrap_or_default();
+
+            mark_security(&mut updates, &zypper_security_packages(), |package, name| package == name);
+            updates
+        },
+        PackageManager::Unknown => Vec::new(),
+    }
+}
+
+// Güvenlik kaydıyla eşleşen güncellemeleri işaretler; eşleştirme ölçütü paket yöneticisine göre değişir
+fn mark_security<T>(
+    updates: &mut [PackageUpdate],
+    advisories: &[(T, Option<String>)],
+    matches: impl Fn(&T, &str) -> bool,
+) {
+    for update in updates.iter_mut() {
+        if let Some((_, severity)) = advisories.iter().find(|(advisory, _)| matches(advisory, &update.name)) {
+            update.is_security = true;
+            update.severity = severity.clone();
+        }
+    }
+}
+
+// openssl/jammy-updates,jammy-security 3.0.2-0ubuntu1.15 amd64 [upgradable from: 3.0.2-0ubuntu1.14]
+fn parse_apt_upgradable(output: &str) -> Vec<PackageUpdate> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let (name, repository) = fields.next()?.split_once('/')?;
+            let new_version = fields.next()?.to_string();
+            let current_version = line
+                .split_once("[upgradable from:")
+                .map(|(_, rest)| rest.trim().trim_end_matches(']').trim().to_string());
+
+            Some(PackageUpdate {
+                name: name.to_string(),
+                current_version,
+                new_version,
+                repository: Some(repository.to_string()),
+                is_security: repository.split(',').any(|r| r.contains("-security")),
+                severity: None,
+            })
+        })
+        .collect()
+}
+
+// kernel.x86_64    4.18.0-513.el8    baseos
+fn parse_yum_check_update(output: &str) -> Vec<PackageUpdate> {
+    let mut updates = Vec::new();
+    let mut wrapped: Option<&str> = None;
+    for line in output.lines() {
+        // Obsolete paket listesi güncelleme değildir
+        if line.starts_with("Obsoleting Packages") {
+            break;
+        }
+        let mut fields: Vec<&str> = line.split_whitespace().collect();
+        // Uzun paket adları sürüm ve depo bilgisini bir alt satıra kaydırır
+        if let Some(name) = wrapped.take() {
+            if line.starts_with(char::is_whitespace) && fields.len() == 2 {
+                fields.insert(0, name);
+            }
+        } else if fields.len() == 1 && !line.starts_with(char::is_whitespace) {
+            wrapped = Some(fields[0]);
+            continue;
+        }
+        if fields.len() != 3 || (line.starts_with(char::is_whitespace) && fields[0].starts_with('*')) {
+            continue;
+        }
+        let Some((name, _arch)) = fields[0].rsplit_once('.') else {
+            continue;
+        };
+        if !fields[1].contains(|c: char| c.is_ascii_digit()) {
+            continue;
+        }
+        updates.push(PackageUpdate {
+            name: name.to_string(),
+            current_version: None,
+            new_version: fields[1].to_string(),
+            repository: Some(fields[2].to_string()),
+            is_security: false,
+            severity: None,
+        });
+    }
+    updates
+}
+
+// RHSA-2023:7877 Important/Sec. openssl-libs-1:1.1.1k-12.el8_9.x86_64
+fn parse_yum_updateinfo(output: &str) -> Vec<(String, Option<String>)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 3 || !fields[1].contains("Sec") {
+                return None;
+            }
+            let severity = fields[1]
+                .split_once('/')
+                .map(|(s, _)| s.to_string())
+                .filter(|s| s != "None");
+            Some((fields[2].to_string(), severity))
+        })
+        .collect()
+}
+
+fn nevra_matches(nevra: &str, name: &str) -> bool {
+    nevra
+        .strip_prefix(name)
+        .and_then(|rest| rest.strip_prefix('-'))
+        .map(|rest| rest.starts_with(|c: char| c.is_ascii_digit()))
+        .unwrap_or(false)
+}
+
+// linux 6.1.1.arch1-1 -> 6.1.2.arch1-1
+fn parse_pacman_updates(output: &str) -> Vec<PackageUpdate> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 4 || fields[2] != "->" {
+                return None;
+            }
+            Some(PackageUpdate {
+                name: fields[0].to_string(),
+                current_version: Some(fields[1].to_string()),
+                new_version: fields[3].to_string(),
+                repository: None,
+                is_security: false,
+                severity: None,
+            })
+        })
+        .collect()
+}
+
+fn parse_arch_audit(output: &str) -> Vec<(String, Option<String>)> {
+    output
+        .lines()
+        .filter_map(|line| line.split_once('|'))
+        .map(|(name, severity)| (name.trim().to_string(), Some(severity.trim().to_string())))
+        .collect()
+}
+
+// v | Main Update Repository | openssl-1_1 | 1.1.1l-150400.7.25.1 | 1.1.1l-150400.7.28.1 | x86_64
+fn parse_zypper_list_updates(output: &str) -> Vec<PackageUpdate> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let columns: Vec<&str> = line.split('|').map(str::trim).collect();
+            if columns.len() < 6 || columns[0] != "v" {
+                return None;
+            }
+            Some(PackageUpdate {
+                name: columns[2].to_string(),
+                current_version: Some(columns[3].to_string()),
+                new_version: columns[4].to_string(),
+                repository: Some(columns[1].to_string()),
+                is_security: false,
+                severity: None,
+            })
+        })
+        .collect()
+}
+
+// Bekleyen güvenlik yamalarının güncellediği paketler ve yamanın önem derecesi
+fn zypper_security_packages() -> Vec<(String, Option<String>)> {
+    let Some(out) = command_stdout(
+        "zypper",
+        &["--xmlout", "--non-interactive", "--no-refresh", "list-patches", "--category", "security"],
+    ) else {
+        return Vec::new();
+    };
+    let patches = parse_zypper_patch_list(&out);
+    if patches.is_empty() {
+        return Vec::new();
+    }
+
+    // Alan başlıkları yerelleştirildiğinden info çıktısı C yereliyle alınır
+    let Ok(output) = Command::new("zypper")
+        .env("LC_ALL", "C")
+        .args(["--non-interactive", "--no-refresh", "info", "-t", "patch"])
+        .args(patches.keys())
+        .output()
+    else {
+        return Vec::new();
+    };
+    patch_packages(&patches, parse_zypper_patch_info(&String::from_utf8_lossy(&output.stdout)))
+}
+
+// Yama -> paket listesini paket -> yama önem derecesi listesine çevirir
+fn patch_packages(
+    patches: &BTreeMap<String, Option<String>>,
+    info: BTreeMap<String, Vec<String>>,
+) -> Vec<(String, Option<String>)> {
+    info.into_iter()
+        .flat_map(|(patch, packages)| {
+            let severity = patches.get(&patch).cloned().flatten();
+            packages.into_iter().map(move |package| (package, severity.clone()))
+        })
+        .collect()
+}
+
+// <update kind="patch" name="..." status="needed" category="security" severity="important" ...>
+fn parse_zypper_patch_list(output: &str) -> BTreeMap<String, Option<String>> {
+    static UPDATE: OnceLock<Regex> = OnceLock::new();
+    static ATTRIBUTE: OnceLock<Regex> = OnceLock::new();
+    let update = UPDATE.get_or_init(|| Regex::new(r"<update\s[^>]*>").unwrap());
+    let attribute = ATTRIBUTE.get_or_init(|| Regex::new(r#"([\w-]+)="([^"]*)""#).unwrap());
+
+    update
+        .find_iter(output)
+        .filter_map(|tag| {
+            let attributes: BTreeMap<&str, &str> = attribute
+                .captures_iter(tag.as_str())
+                .map(|caps| (caps.get(1).unwrap().as_str(), caps.get(2).unwrap().as_str()))
+                .collect();
+            if attributes.get("kind") != Some(&"patch")
+                || attributes.get("category") != Some(&"security")
+                || !attributes.get("status").map(|s| s.starts_with("needed")).unwrap_or(false)
+            {
+                return None;
+            }
+            let severity = attributes
+                .get("severity")
+                .filter(|s| !s.is_empty() && **s != "unspecified")
+                .map(|s| s.to_string());
+            Some((attributes.get("name")?.to_string(), severity))
+        })
+        .collect()
+}
+
+// "Information for patch X:" bölümlerinde Conflicts altında yamanın güncellediği paketler listelenir:
+//     openssl-1_1.x86_64 < 1.1.1l-150400.7.28.1
+fn parse_zypper_patch_info(output: &str) -> BTreeMap<String, Vec<String>> {
+    const ARCHES: &[&str] = &["noarch", "x86_64", "i586", "i686", "aarch64", "ppc64le", "s390x", "armv7hl"];
+    let mut patches: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let mut current: Option<String> = None;
+    let mut in_conflicts = false;
+
+    for line in output.lines() {
+        if let Some(name) = line.strip_prefix("Information for patch ") {
+            current = Some(name.trim_end_matches(':').to_string());
+            in_conflicts = false;
+            continue;
+        }
+        if !line.starts_with(char::is_whitespace) {
+            in_conflicts = line.starts_with("Conflicts");
+            continue;
+        }
+        let (Some(patch), true) = (&current, in_conflicts) else {
+            continue;
+        };
+        // Kaynak paketler (srcpackage:) kurulu paket değildir
+        let Some(package) = line.split_whitespace().next().filter(|p| !p.contains(':')) else {
+            continue;
+        };
+        let name = match package.rsplit_once('.') {
+            Some((name, arch)) if ARCHES.contains(&arch) => name,
+            _ => package,
+        };
+        let packages = patches.entry(patch.clone()).or_default();
+        if !packages.iter().any(|p| p == name) {
+            packages.push(name.to_string());
+        }
+    }
+    patches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_apt_list_upgradable() {
+        let updates = parse_apt_upgradable(include_str!("../tests/fixtures/packages/apt_list_upgradable.txt"));
+        assert_eq!(updates.len(), 3);
+
+        assert_eq!(updates[0].name, "libssl3");
+        assert_eq!(updates[0].new_version, "3.0.2-0ubuntu1.15");
+        assert_eq!(updates[0].current_version.as_deref(), Some("3.0.2-0ubuntu1.14"));
+        assert_eq!(updates[0].repository.as_deref(), Some("jammy-updates,jammy-security"));
+        assert!(updates[0].is_security);
+
+        assert_eq!(updates[2].name, "snapd");
+        assert!(!updates[2].is_security);
+    }
+
+    #[test]
+    fn parses_yum_check_update_and_security_advisories() {
+        let mut updates = parse_yum_check_update(include_str!("../tests/fixtures/packages/yum_check_update.txt"));
+        assert_eq!(updates.len(), 3);
+        assert_eq!(updates[0].name, "kernel");
+        assert_eq!(updates[0].new_version, "4.18.0-513.24.1.el8_9");
+        assert_eq!(updates[0].repository.as_deref(), Some("baseos"));
+        assert_eq!(updates[1].name, "openssl-libs");
+        assert_eq!(updates[1].new_version, "1:1.1.1k-12.el8_9");
+
+        let advisories = parse_yum_updateinfo(include_str!("../tests/fixtures/packages/yum_updateinfo_security.txt"));
+        assert_eq!(advisories.len(), 2);
+        assert_eq!(advisories[0].1.as_deref(), Some("Important"));
+
+        mark_security(&mut updates, &advisories, |nevra, name| nevra_matches(nevra, name));
+        assert!(updates[1].is_security);
+        assert_eq!(updates[1].severity.as_deref(), Some("Moderate"));
+        // openssl paketi openssl-libs ile karışmamalı
+        assert!(!updates[2].is_security);
+    }
+
+    #[test]
+    fn parses_dnf_check_update() {
+        let updates = parse_yum_check_update(include_str!("../tests/fixtures/packages/dnf_check_update.txt"));
+        let names: Vec<&str> = updates.iter().map(|u| u.name.as_str()).collect();
+        assert_eq!(names, vec!["curl", "libcurl-minimal-with-a-very-long-name"]);
+        assert_eq!(updates[1].repository.as_deref(), Some("updates"));
+    }
+
+    #[test]
+    fn parses_pacman_updates() {
+        let updates = parse_pacman_updates(include_str!("../tests/fixtures/packages/pacman_qu.txt"));
+        assert_eq!(updates.len(), 2);
+        assert_eq!(updates[0].name, "linux");
+        assert_eq!(updates[0].current_version.as_deref(), Some("6.1.1.arch1-1"));
+        assert_eq!(updates[0].new_version, "6.1.2.arch1-1");
+
+        let affected = parse_arch_audit("openssl|High\n");
+        assert_eq!(affected, vec![("openssl".to_string(), Some("High".to_string()))]);
+    }
+
+    #[test]
+    fn parses_zypper_list_updates_and_patches() {
+        let mut updates = parse_zypper_list_updates(include_str!("../tests/fixtures/packages/zypper_list_updates.txt"));
+        assert_eq!(updates.len(), 2);
+        // "S" ile başlayan paketler başlık satırıyla karışmamalı
+        assert_eq!(updates[0].name, "SDL2");
+        assert_eq!(updates[1].name, "openssl-1_1");
+        assert_eq!(updates[1].current_version.as_deref(), Some("1.1.1l-150400.7.25.1"));
+        assert_eq!(updates[1].repository.as_deref(), Some("Update repository with updates from SUSE Linux Enterprise 15"));
+
+        let patches = parse_zypper_patch_list(include_str!("../tests/fixtures/packages/zypper_list_patches.xml"));
+        assert_eq!(patches.len(), 2);
+        assert_eq!(patches["openSUSE-SLE-15.4-2023-1234"].as_deref(), Some("important"));
+        assert_eq!(patches["openSUSE-SLE-15.4-2023-1301"].as_deref(), Some("low"));
+
+        let info = parse_zypper_patch_info(include_str!("../tests/fixtures/packages/zypper_info_patch.txt"));
+        assert_eq!(
+            info["openSUSE-SLE-15.4-2023-1234"],
+            vec!["libopenssl-1_1-devel", "libopenssl1_1", "openssl-1_1"]
+        );
+        // Özet metni yerelleştirilmiş olsa da paket listesi Conflicts bölümünden gelir
+        assert_eq!(info["openSUSE-SLE-15.4-2023-1301"], vec!["SDL2", "libSDL2-2_0-0"]);
+
+        mark_security(&mut updates, &patch_packages(&patches, info), |package, name| package == name);
+        assert_eq!(updates[0].severity.as_deref(), Some("low"));
+        assert!(updates[1].is_security);
+        assert_eq!(updates[1].severity.as_deref(), Some("important"));
+    }
+}
@@ -0,0 +1,519 @@
+use crate::config::{ProcessConfig, ProcessRule, ProcessSort};
+use crate::{glob_match, Finding, Severity};
+use log::warn;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+use sysinfo::{Pid, PidExt, Process, ProcessExt, ProcessStatus, System, SystemExt, UserExt};
+
+// Yapılandırma çalışma sırasında değişmez, desenler bir kez derlenir
+static REDACT_PATTERNS: OnceLock<Vec<Regex>> = OnceLock::new();
+
+// Kural desenleri ilk kullanımda derlenir; geçersiz desen bir kez loglanır
+static RULE_PATTERNS: Mutex<BTreeMap<String, Option<Regex>>> = Mutex::new(BTreeMap::new());
+
+// (pid, başlangıç zamanı) -> zombi/D durumunun ilk görüldüğü an; PID yeniden kullanımına karşı başlangıç zamanı da anahtarda
+static STUCK_SINCE: Mutex<BTreeMap<(u32, u64), Instant>> = Mutex::new(BTreeMap::new());
+
+#[derive(Debug, Serialize)]
+pub struct ProcessInfo {
+    pub pid: u32,
+    pub ppid: Option<u32>,
+    pub name: String,
+    pub user: Option<String>,
+    pub uid: Option<u32>,
+    pub cpu_usage: f32,
+    pub memory_usage: u64,
+    pub threads: Option<u32>,
+    pub status: String,
+    pub start_time: Option<String>,
+    pub command: Option<String>,
+    pub container_id: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProcessTreeNode {
+    pub pid: u32,
+    pub name: String,
+    pub children: Vec<ProcessTreeNode>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StuckProcess {
+    pub pid: u32,
+    // Zombiler için süreci toplamayan ebeveyn
+    pub ppid: Option<u32>,
+    pub name: String,
+    pub state: String,
+    pub duration_seconds: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProcessSummary {
+    pub total: usize,
+    pub zombie_count: usize,
+    pub uninterruptible_count: usize,
+    pub stuck_processes: Vec<StuckProcess>,
+    pub processes: Vec<ProcessInfo>,
+    pub tree: Option<Vec<ProcessTreeNode>>,
+    pub findings: Vec<Finding>,
+}
+
+// Kurallar, takılı süreçler ve ağaç için sysinfo'dan bağımsız süreç görüntüsü
+#[derive(Debug, Clone)]
+struct ProcessEntry {
+    pid: u32,
+    ppid: Option<u32>,
+    name: String,
+    exe: String,
+    cmdline: String,
+    status: ProcessStatus,
+    start_time: u64,
+}
+
+impl ProcessEntry {
+    fn from_process(process: &Process) -> Self {
+        Self {
+            pid: process.pid().as_u32(),
+            ppid: process.parent().map(|p| p.as_u32()),
+            name: process.name().to_string(),
+            exe: process.exe().to_string_lossy().into_owned(),
+            cmdline: process.cmd().join(" "),
+            status: process.status(),
+            start_time: process.start_time(),
+        }
+    }
+}
+
+pub fn get_process_summary(sys: &System, config: &ProcessConfig) -> ProcessSummary {
+    let patterns = REDACT_PATTERNS.get_or_init(|| compile_redact_patterns(&config.redact_patterns));
+    let own_pid = std::process::id();
+    let entries: Vec<ProcessEntry> = sys.processes().values().map(ProcessEntry::from_process).collect();
+
+    let mut processes: Vec<&Process> = sys.processes().values().collect();
+    match config.sort_by {
+        ProcessSort::Cpu => processes.sort_by(|a, b| b.cpu_usage().total_cmp(&a.cpu_usage())),
+        ProcessSort::Memory => processes.sort_by_key(|p| std::cmp::Reverse(p.memory())),
+    }
+    if config.top_n > 0 {
+        processes.truncate(config.top_n);
+    }
+
+    let list = processes
+        .into_iter()
+        .map(|process| {
+            let uid = process.user_id().map(|uid| **uid);
+            ProcessInfo {
+                pid: process.pid().as_u32(),
+                ppid: process.parent().map(|p| p.as_u32()),
+                name: process.name().to_string(),
+                // Çözülemeyen kullanıcılar root gibi görünmesin, sayısal UID olarak kalır
+                user: process
+                    .user_id()
+                    .map(|uid| sys.get_user_by_id(uid).map(|u| u.name().to_string()).unwrap_or_else(|| uid.to_string())),
+                uid,
+                cpu_usage: process.cpu_usage(),
+                memory_usage: process.memory(),
+                threads: thread_count(process.pid()),
+                status: process.status().to_string(),
+                start_time: chrono::DateTime::from_timestamp(process.start_time() as i64, 0)
+                    .filter(|_| process.start_time() != 0)
+                    .map(|dt| dt.with_timezone(&chrono::Local).to_rfc3339()),
+                command: config
+                    .include_command_line
+                    .then(|| redact(&process.cmd().join(" "), patterns))
+                    .filter(|cmd| !cmd.is_empty()),
+                container_id: crate::containers::container_id_for_pid(process.pid().as_u32()),
+            }
+        })
+        .collect();
+
+    let zombie_count = entries.iter().filter(|p| p.status == ProcessStatus::Zombie).count();
+    let uninterruptible_count = entries
+        .iter()
+        .filter(|p| p.status == ProcessStatus::UninterruptibleDiskSleep)
+        .count();
+
+    ProcessSummary {
+        total: entries.len(),
+        zombie_count,
+        uninterruptible_count,
+        stuck_processes: get_stuck_processes(&entries, config.stuck_threshold_seconds),
+        processes: list,
+        tree: config.tree.then(|| build_tree(&entries)),
+        // Daemonun kendisi kurallara takılmasın
+        findings: evaluate_rules(entries.iter().filter(|p| p.pid != own_pid), config),
+    }
+}
+
+fn compile_redact_patterns(patterns: &[String]) -> Vec<Regex> {
+    patterns
+        .iter()
+        .filter_map(|pattern| match Regex::new(pattern) {
+            Ok(regex) => Some(regex),
+            Err(e) => {
+                warn!("Geçersiz komut satırı maskeleme deseni ({}): {}", pattern, e);
+                None
+            }
+        })
+        .collect()
+}
+
+// Eşikten uzun süredir zombi veya kesintisiz uykuda (D) kalan süreçler
+fn get_stuck_processes(entries: &[ProcessEntry], threshold_seconds: u64) -> Vec<StuckProcess> {
+    let mut since = STUCK_SINCE.lock().unwrap_or_else(|e| e.into_inner());
+    track_stuck(entries, threshold_seconds, Instant::now(), &mut since)
+}
+
+fn track_stuck(
+    entries: &[ProcessEntry],
+    threshold_seconds: u64,
+    now: Instant,
+    since: &mut BTreeMap<(u32, u64), Instant>,
+) -> Vec<StuckProcess> {
+    let mut current = BTreeMap::new();
+    let mut stuck = Vec::new();
+    for process in entries {
+        let state = match process.status {
+            ProcessStatus::Zombie => "zombie",
+            ProcessStatus::UninterruptibleDiskSleep => "uninterruptible",
+            _ => continue,
+        };
+        let key = (process.pid, process.start_time);
+        let first_seen = since.get(&key).copied().unwrap_or(now);
+        current.insert(key, first_seen);
+
+        let duration = now.duration_since(first_seen).as_secs();
+        if duration >= threshold_seconds {
+            stuck.push(StuckProcess {
+                pid: process.pid,
+                ppid: process.ppid,
+                name: process.name.clone(),
+                state: state.to_string(),
+                duration_seconds: duration,
+            });
+        }
+    }
+    // Durumdan çıkan süreçlerin sayacı sıfırlanır
+    *since = current;
+
+    stuck.sort_by_key(|p| p.pid);
+    stuck
+}
+
+// Kurallar ilk N süreçle sınırlı listeye değil, tüm süreçlere uygulanır
+fn evaluate_rules<'a>(entries: impl IntoIterator<Item = &'a ProcessEntry>, config: &ProcessConfig) -> Vec<Finding> {
+    let entries: Vec<&ProcessEntry> = entries.into_iter().collect();
+    let mut findings = Vec::new();
+
+    for rule in &config.required {
+        let matched = matching_pids(&entries, rule);
+        let min = rule.min_instances.unwrap_or(1);
+        if matched.len() < min {
+            findings.push(Finding {
+                check: format!("process.required.{}", rule.name),
+                severity: Severity::Critical,
+                message: format!("Gerekli süreç eksik: {} ({} çalışıyor, en az {})", rule.name, matched.len(), min),
+                expected: Some(format!(">={}", min)),
+                actual: Some(matched.len().to_string()),
+            });
+        }
+        if let Some(max) = rule.max_instances.filter(|max| matched.len() > *max) {
+            findings.push(Finding {
+                check: format!("process.required.{}", rule.name),
+                severity: Severity::Warning,
+                message: format!("Süreç sayısı fazla: {} ({} çalışıyor, en çok {})", rule.name, matched.len(), max),
+                expected: Some(format!("<={}", max)),
+                actual: Some(matched.len().to_string()),
+            });
+        }
+    }
+
+    for rule in &config.forbidden {
+        let matched = matching_pids(&entries, rule);
+        if !matched.is_empty() {
+            let pids: Vec<String> = matched.iter().map(|pid| pid.to_string()).collect();
+            findings.push(Finding {
+                check: format!("process.forbidden.{}", rule.name),
+                severity: Severity::Critical,
+                message: format!("Yasaklı süreç çalışıyor: {} (PID {})", rule.name, pids.join(", ")),
+                expected: Some("0".to_string()),
+                actual: Some(pids.join(",")),
+            });
+        }
+    }
+
+    findings
+}
+
+fn matching_pids(entries: &[&ProcessEntry], rule: &ProcessRule) -> Vec<u32> {
+    if rule.process_name.is_none() && rule.exe.is_none() && rule.cmdline.is_none() {
+        return Vec::new();
+    }
+    let cmdline = match &rule.cmdline {
+        Some(pattern) => match rule_pattern(pattern) {
+            Some(regex) => Some(regex),
+            None => return Vec::new(),
+        },
+        None => None,
+    };
+
+    let mut pids: Vec<u32> = entries
+        .iter()
+        .filter(|p| rule.process_name.as_ref().map(|pat| glob_match(pat, &p.name)).unwrap_or(true))
+        .filter(|p| rule.exe.as_ref().map(|pat| glob_match(pat, &p.exe)).unwrap_or(true))
+        .filter(|p| cmdline.as_ref().map(|re| re.is_match(&p.cmdline)).unwrap_or(true))
+        .map(|p| p.pid)
+        .collect();
+    pids.sort_unstable();
+    pids
+}
+
+fn rule_pattern(pattern: &str) -> Option<Regex> {
+    let mut cache = RULE_PATTERNS.lock().unwrap_or_else(|e| e.into_inner());
+    cache
+        .entry(pattern.to_string())
+        .or_insert_with(|| match Regex::new(pattern) {
+            Ok(regex) => Some(regex),
+            Err(e) => {
+                warn!("Geçersiz süreç kuralı deseni ({}): {}", pattern, e);
+                None
+            }
+        })
+        .clone()
+}
+
+fn thread_count(pid: Pid) -> Option<u32> {
+    // /proc/<pid>/status: "Threads:	4"
+    fs::read_to_string(format!("/proc/{}/status", pid.as_u32()))
+        .ok()?
+        .lines()
+        .find_map(|line| line.strip_prefix("Threads:"))
+        .and_then(|v| v.trim().parse().ok())
+}
+
+fn redact(command: &str, patterns: &[Regex]) -> String {
+    // İlk yakalama grubu (örn. "--password=") korunur, kalan eşleşme maskelenir
+    patterns.iter().fold(command.to_string(), |cmd, regex| {
+        regex
+            .replace_all(&cmd, |caps: &regex::Captures| {
+                format!("{}***", caps.get(1).map(|m| m.as_str()).unwrap_or(""))
+            })
+            .into_owned()
+    })
+}
+
+fn build_tree(entries: &[ProcessEntry]) -> Vec<ProcessTreeNode> {
+    let names: BTreeMap<u32, &str> = entries.iter().map(|p| (p.pid, p.name.as_str())).collect();
+    let mut children: BTreeMap<Option<u32>, Vec<u32>> = BTreeMap::new();
+    for process in entries {
+        // Ebeveyni listede olmayan süreçler köke bağlanır
+        let parent = process.ppid.filter(|ppid| names.contains_key(ppid));
+        children.entry(parent).or_default().push(process.pid);
+    }
+    for pids in children.values_mut() {
+        pids.sort_unstable();
+    }
+
+    fn node(pid: u32, names: &BTreeMap<u32, &str>, children: &BTreeMap<Option<u32>, Vec<u32>>) -> ProcessTreeNode {
+        ProcessTreeNode {
+            pid,
+            name: names.get(&pid).map(|name| name.to_string()).unwrap_or_default(),
+            children: children
+                .get(&Some(pid))
+                .map(|pids| pids.iter().map(|child| node(*child, names, children)).collect())
+                .unwrap_or_default(),
+        }
+    }
+
+    children
+        .get(&None)
+        .map(|roots| roots.iter().map(|pid| node(*pid, &names, &children)).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn entry(pid: u32, name: &str, cmdline: &str) -> ProcessEntry {
+        ProcessEntry {
+            pid,
+            ppid: Some(1),
+            name: name.to_string(),
+            exe: format!("/usr/bin/{}", name),
+            cmdline: cmdline.to_string(),
+            status: ProcessStatus::Sleep,
+            start_time: 1_700_000_000,
+        }
+    }
+
+    fn rule(name: &str, process_name: &str) -> ProcessRule {
+        ProcessRule {
+            name: name.to_string(),
+            process_name: Some(process_name.to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn checks(findings: &[Finding]) -> Vec<(&str, Severity)> {
+        findings.iter().map(|f| (f.check.as_str(), f.severity)).collect()
+    }
+
+    #[test]
+    fn evaluates_required_instance_counts() {
+        let entries = vec![
+            entry(10, "nginx", "nginx: master process /usr/sbin/nginx"),
+            entry(11, "nginx", "nginx: worker process"),
+            entry(12, "nginx", "nginx: worker process"),
+            entry(20, "sshd", "/usr/sbin/sshd -D"),
+        ];
+        let config = ProcessConfig {
+            required: vec![
+                rule("sshd", "sshd"),
+                ProcessRule { min_instances: Some(2), ..rule("postgres", "postgres*") },
+                ProcessRule { min_instances: Some(2), max_instances: Some(3), ..rule("nginx-ok", "nginx") },
+                ProcessRule { max_instances: Some(2), ..rule("nginx-many", "nginx") },
+                // Ölçütsüz kural hiçbir süreçle eşleşmez
+                ProcessRule { name: "empty".to_string(), ..Default::default() },
+            ],
+            forbidden: Vec::new(),
+            ..Default::default()
+        };
+
+        let findings = evaluate_rules(&entries, &config);
+        assert_eq!(
+            checks(&findings),
+            vec![
+                ("process.required.postgres", Severity::Critical),
+                ("process.required.nginx-many", Severity::Warning),
+                ("process.required.empty", Severity::Critical),
+            ]
+        );
+        assert_eq!(findings[0].actual.as_deref(), Some("0"));
+        assert_eq!(findings[1].expected.as_deref(), Some("<=2"));
+        assert_eq!(findings[1].actual.as_deref(), Some("3"));
+    }
+
+    #[test]
+    fn default_forbidden_rules_catch_miners_and_listeners() {
+        let config = ProcessConfig {
+            required: Vec::new(),
+            ..Default::default()
+        };
+        let entries = vec![
+            entry(100, "xmrig", "./xmrig -o stratum+tcp://pool.example.com:3333 -u wallet"),
+            entry(101, "python3", "python3 miner.py --url Stratum+SSL://pool.example.com:443"),
+            entry(200, "nc", "nc -lvnp 4444"),
+            entry(201, "ncat", "/usr/bin/ncat --keep-open -e /bin/sh -l 9001"),
+            entry(202, "nc.openbsd", "nc.openbsd -l 8080"),
+            // Dinlemeyen netcat ve adında "nc" geçen diğer araçlar yasak değil
+            entry(300, "nc", "nc -zv db.internal 5432"),
+            entry(301, "rsync", "rsync -l src/ dst/"),
+            entry(302, "vim", "vim /etc/nc.conf -l"),
+        ];
+
+        let findings = evaluate_rules(&entries, &config);
+        assert_eq!(
+            checks(&findings),
+            vec![
+                ("process.forbidden.cryptominer", Severity::Critical),
+                ("process.forbidden.netcat-listener", Severity::Critical),
+            ]
+        );
+        assert_eq!(findings[0].actual.as_deref(), Some("100,101"));
+        assert_eq!(findings[1].actual.as_deref(), Some("200,201,202"));
+    }
+
+    #[test]
+    fn matches_exe_globs_and_skips_invalid_patterns() {
+        let mut java = entry(30, "java", "java -jar /opt/app/app.jar");
+        java.exe = "/usr/lib/jvm/java-17/bin/java".to_string();
+        let entries = [java, entry(31, "java", "java -version")];
+        let all: Vec<&ProcessEntry> = entries.iter().collect();
+
+        let by_exe = ProcessRule {
+            exe: Some("/usr/lib/jvm/*".to_string()),
+            ..rule("jvm", "java")
+        };
+        assert_eq!(matching_pids(&all, &by_exe), vec![30]);
+
+        let invalid = ProcessRule {
+            cmdline: Some("(unclosed".to_string()),
+            ..rule("invalid", "java")
+        };
+        assert!(matching_pids(&all, &invalid).is_empty());
+    }
+
+    #[test]
+    fn redacts_secrets_in_command_lines() {
+        let patterns = compile_redact_patterns(&ProcessConfig::default().redact_patterns);
+        assert_eq!(
+            redact("mysqldump --password=hunter2 --user=backup db", &patterns),
+            "mysqldump --password=*** --user=backup db"
+        );
+        assert_eq!(
+            redact("agent --token abc123 -api-key=XYZ --verbose", &patterns),
+            "agent --token *** -api-key=*** --verbose"
+        );
+        assert_eq!(
+            redact("psql postgres://admin:s3cret@db:5432/app", &patterns),
+            "psql postgres://admin:***@db:5432/app"
+        );
+        assert_eq!(redact("/usr/sbin/sshd -D", &patterns), "/usr/sbin/sshd -D");
+        // Geçersiz desenler atlanır, geçerliler uygulanmaya devam eder
+        let patterns = compile_redact_patterns(&["(".to_string(), r"(--secret=)\S+".to_string()]);
+        assert_eq!(patterns.len(), 1);
+        assert_eq!(redact("app --secret=x", &patterns), "app --secret=***");
+    }
+
+    #[test]
+    fn reports_processes_stuck_past_threshold() {
+        let mut zombie = entry(40, "defunct", "");
+        zombie.ppid = Some(39);
+        zombie.status = ProcessStatus::Zombie;
+        let mut blocked = entry(41, "rsync", "rsync -a /mnt/nfs /backup");
+        blocked.status = ProcessStatus::UninterruptibleDiskSleep;
+        let running = entry(42, "bash", "bash");
+
+        let start = Instant::now();
+        let mut since = BTreeMap::new();
+        let entries = vec![zombie.clone(), blocked.clone(), running];
+        assert!(track_stuck(&entries, 60, start, &mut since).is_empty());
+        assert_eq!(since.len(), 2);
+
+        let stuck = track_stuck(&entries, 60, start + Duration::from_secs(60), &mut since);
+        assert_eq!(stuck.len(), 2);
+        assert_eq!((stuck[0].pid, stuck[0].ppid, stuck[0].state.as_str()), (40, Some(39), "zombie"));
+        assert_eq!((stuck[1].pid, stuck[1].state.as_str()), (41, "uninterruptible"));
+        assert_eq!(stuck[1].duration_seconds, 60);
+
+        // D durumundan çıkan sürecin sayacı sıfırlanır; aynı PID yeni başlangıç zamanıyla yeni süreçtir
+        let mut reused = zombie;
+        reused.start_time += 120;
+        let stuck = track_stuck(&[reused], 60, start + Duration::from_secs(120), &mut since);
+        assert!(stuck.is_empty());
+        assert_eq!(since.len(), 1);
+    }
+
+    #[test]
+    fn builds_tree_with_orphans_at_root() {
+        let mut init = entry(1, "systemd", "/sbin/init");
+        init.ppid = None;
+        let mut child = entry(50, "sshd", "sshd");
+        child.ppid = Some(1);
+        let mut grandchild = entry(51, "bash", "-bash");
+        grandchild.ppid = Some(50);
+        let mut orphan = entry(60, "kworker", "");
+        orphan.ppid = Some(2);
+
+        let tree = build_tree(&[grandchild, orphan, child, init]);
+        assert_eq!(tree.iter().map(|n| n.pid).collect::<Vec<_>>(), vec![1, 60]);
+        assert_eq!(tree[0].children[0].name, "sshd");
+        assert_eq!(tree[0].children[0].children[0].pid, 51);
+    }
+}
@@ -0,0 +1,603 @@
+use crate::config::RebootConfig;
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
+use log::info;
+use regex::Regex;
+use serde::Serialize;
+use serde_json::Value;
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+// systemd katalog kimliği: "System shutdown initiated"
+const SHUTDOWN_MESSAGE_ID: &str = "98268866d1d54a499c4e98921d93bc40";
+const MAX_BOOTS: usize = 20;
+
+// Geçmiş açılışlar çalışma sırasında değişmez, yalnızca bir kez toplanır
+static BOOT_HISTORY: OnceLock<Vec<BootRecord>> = OnceLock::new();
+
+// needs-restarting ve tüm süreçlerin maps dosyalarının taranması pahalıdır; sonuç aralık boyunca tekrar kullanılır
+static LAST_STATUS: Mutex<Option<(Instant, RebootStatus)>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DeletedLibraryProcess {
+    pub pid: u32,
+    pub name: String,
+    pub libraries: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShutdownKind {
+    Running,
+    Clean,
+    Crash,
+    PowerLoss,
+    // wtmp kapanış kaydı yok; çökme ile güç kaybı ayırt edilemiyor
+    Unclean,
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BootRecord {
+    pub boot_id: Option<String>,
+    pub started_at: String,
+    pub ended_at: Option<String>,
+    pub duration_seconds: Option<u64>,
+    // Bu açılışın nasıl sona erdiği
+    pub shutdown: ShutdownKind,
+    #[serde(skip)]
+    started: DateTime<Local>,
+}
+
+#[derive(Clone)]
+pub struct RebootStatus {
+    pub required: bool,
+    pub reasons: Vec<String>,
+    pub deleted_library_processes: Vec<DeletedLibraryProcess>,
+}
+
+pub fn get_reboot_status(config: &RebootConfig) -> RebootStatus {
+    let mut last = LAST_STATUS.lock().unwrap_or_else(|e| e.into_inner());
+    let interval = Duration::from_secs(config.interval_minutes * 60);
+    if let Some((at, status)) = last.as_ref() {
+        if at.elapsed() < interval {
+            return status.clone();
+        }
+    }
+
+    let status = check_reboot_status();
+    *last = Some((Instant::now(), status.clone()));
+    status
+}
+
+fn check_reboot_status() -> RebootStatus {
+    let mut reasons = Vec::new();
+
+    // Debian/Ubuntu: paket kurulumu sonrası oluşturulan işaret dosyası
+    if Path::new("/var/run/reboot-required").exists() {
+        let packages: Vec<String> = fs::read_to_string("/var/run/reboot-required.pkgs")
+            .map(|content| {
+                content
+                    .lines()
+                    .map(str::trim)
+                    .filter(|l| !l.is_empty())
+                    .collect::<BTreeSet<_>>()
+                    .into_iter()
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default();
+        if packages.is_empty() {
+            reasons.push("/var/run/reboot-required mevcut".to_string());
+        } else {
+            reasons.push(format!("/var/run/reboot-required mevcut: {}", packages.join(", ")));
+        }
+    }
+
+    // RHEL/CentOS/Fedora: needs-restarting -r yeniden başlatma gerekiyorsa 1 döner
+    if let Ok(output) = Command::new("needs-restarting").arg("-r").output() {
+        if output.status.code() == Some(1) {
+            reasons.push("needs-restarting -r yeniden başlatma gerektiğini bildirdi".to_string());
+        }
+    }
+
+    // Çalışan çekirdek ile kurulu en yeni çekirdeği karşılaştır; konteynerde çekirdek ana makineye aittir
+    if !crate::virt::in_container() {
+        if let Ok(running) = fs::read_to_string("/proc/sys/kernel/osrelease") {
+            let running = running.trim();
+            if let Some(newest) = installed_kernels().into_iter().max_by(|a, b| compare_versions(a, b)) {
+                if compare_versions(&newest, running) == Ordering::Greater {
+                    reasons.push(format!("Daha yeni çekirdek kurulu: {} (çalışan: {})", newest, running));
+                }
+            }
+        }
+    }
+
+    RebootStatus {
+        required: !reasons.is_empty(),
+        reasons,
+        deleted_library_processes: get_deleted_library_processes(),
+    }
+}
+
+fn installed_kernels() -> Vec<String> {
+    let mut kernels = BTreeSet::new();
+
+    if let Ok(entries) = fs::read_dir("/boot") {
+        for entry in entries.filter_map(|e| e.ok()) {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if let Some(version) = name.strip_prefix("vmlinuz-") {
+                // Arch'taki vmlinuz-linux gibi sürümsüz adlar atlanır
+                if version.starts_with(|c: char| c.is_ascii_digit()) {
+                    kernels.insert(version.to_string());
+                }
+            }
+        }
+    }
+
+    for dir in ["/lib/modules", "/usr/lib/modules"] {
+        if let Ok(entries) = fs::read_dir(dir) {
+            for entry in entries.filter_map(|e| e.ok()) {
+                // Kaldırılmış çekirdeklerden artık dizinler kalabilir, modules.dep ile doğrula
+                if entry.path().join("modules.dep").exists() {
+                    kernels.insert(entry.file_name().to_string_lossy().into_owned());
+                }
+            }
+        }
+    }
+
+    kernels.into_iter().collect()
+}
+
+// rpmvercmp: sürümler sırayla sayısal ve alfabetik parçalara ayrılıp karşılaştırılır, ayraçlar atlanır.
+// Sayısal parça alfabetik parçadan yenidir (513.24.1.el8 > 513.el8); "~" her şeyden, "^" sonlanmadan eskidir.
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    let (mut a, mut b) = (a.as_bytes(), b.as_bytes());
+    let is_separator = |c: &u8| !c.is_ascii_alphanumeric() && *c != b'~' && *c != b'^';
+
+    loop {
+        while a.first().is_some_and(is_separator) {
+            a = &a[1..];
+        }
+        while b.first().is_some_and(is_separator) {
+            b = &b[1..];
+        }
+
+        match (a.first(), b.first()) {
+            (Some(b'~'), Some(b'~')) => {
+                a = &a[1..];
+                b = &b[1..];
+                continue;
+            }
+            (Some(b'~'), _) => return Ordering::Less,
+            (_, Some(b'~')) => return Ordering::Greater,
+            (Some(b'^'), Some(b'^')) => {
+                a = &a[1..];
+                b = &b[1..];
+                continue;
+            }
+            (Some(b'^'), None) => return Ordering::Greater,
+            (None, Some(b'^')) => return Ordering::Less,
+            (Some(b'^'), _) => return Ordering::Less,
+            (_, Some(b'^')) => return Ordering::Greater,
+            (None, None) => return Ordering::Equal,
+            // Fazladan parçası olan sürüm daha yenidir (26+deb10u1 > 26)
+            (Some(_), None) => return Ordering::Greater,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), Some(_)) => {}
+        }
+
+        let numeric = a[0].is_ascii_digit();
+        let segment = |s: &[u8]| {
+            s.iter()
+                .position(|c| if numeric { !c.is_ascii_digit() } else { !c.is_ascii_alphabetic() })
+                .unwrap_or(s.len())
+        };
+        let (len_a, len_b) = (segment(a), segment(b));
+        if len_b == 0 {
+            // Parça türleri farklı: sayısal olan daha yenidir
+            return if numeric { Ordering::Greater } else { Ordering::Less };
+        }
+        let (seg_a, seg_b) = (&a[..len_a], &b[..len_b]);
+        a = &a[len_a..];
+        b = &b[len_b..];
+
+        let ord = if numeric {
+            let trim = |s: &[u8]| -> usize { s.iter().take_while(|c| **c == b'0').count() };
+            let (seg_a, seg_b) = (&seg_a[trim(seg_a)..], &seg_b[trim(seg_b)..]);
+            seg_a.len().cmp(&seg_b.len()).then_with(|| seg_a.cmp(seg_b))
+        } else {
+            seg_a.cmp(seg_b)
+        };
+        if ord != Ordering::Equal {
+            return ord;
+        }
+    }
+}
+
+// Güncellenmiş ama hala eski (silinmiş) kütüphaneyi kullanan süreçler
+fn get_deleted_library_processes() -> Vec<DeletedLibraryProcess> {
+    let entries = match fs::read_dir("/proc") {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut processes = Vec::new();
+    for entry in entries.filter_map(|e| e.ok()) {
+        let Ok(pid) = entry.file_name().to_string_lossy().parse::<u32>() else {
+            continue;
+        };
+        // Diğer kullanıcıların maps dosyası root olmadan okunamaz
+        let Ok(maps) = fs::read_to_string(entry.path().join("maps")) else {
+            continue;
+        };
+
+        let libraries = deleted_libraries(&maps);
+
+        if !libraries.is_empty() {
+            let name = fs::read_to_string(entry.path().join("comm"))
+                .map(|s| s.trim().to_string())
+                .unwrap_or_default();
+            processes.push(DeletedLibraryProcess {
+                pid,
+                name,
+                libraries: libraries.into_iter().collect(),
+            });
+        }
+    }
+    processes.sort_by_key(|p| p.pid);
+    processes
+}
+
+// 7f0c2a1e5000-7f0c2a1fa000 r-xp 00000000 fd:01 1049 /usr/lib/x86_64-linux-gnu/libssl.so.3 (deleted)
+fn deleted_libraries(maps: &str) -> BTreeSet<String> {
+    maps.lines()
+        .filter_map(|line| line.strip_suffix(" (deleted)"))
+        .filter_map(|line| line.split_whitespace().nth(5))
+        .filter(|path| path.starts_with('/') && !path.starts_with("/dev/shm") && path.contains(".so"))
+        .map(String::from)
+        .collect()
+}
+
+// En yeniden eskiye, tekrarsız açılış listesi
+pub fn get_boot_history() -> Vec<BootRecord> {
+    let mut history = BOOT_HISTORY
+        .get_or_init(|| {
+            let mut boots = match journal_boots() {
+                Some((boots, source)) => {
+                    info!("Açılış geçmişi {} çıktısından okundu", source);
+                    boots
+                }
+                None => {
+                    info!("journal açılış listesi alınamadı, açılış geçmişi wtmp'den (last -x) okunuyor");
+                    wtmp_boots()
+                }
+            };
+            boots.sort_by_key(|b| std::cmp::Reverse(b.started));
+            // Aynı açılış farklı kaynaklardan ya da saniye farkıyla iki kez gelebilir
+            boots.dedup_by(|later, earlier| {
+                (later.boot_id.is_some() && later.boot_id == earlier.boot_id)
+                    || (earlier.started - later.started).num_seconds().abs() < 5
+            });
+            boots.truncate(MAX_BOOTS);
+            boots
+        })
+        .clone();
+
+    // Çalışan açılışın süresi her çağrıda güncellenir
+    let uptime = fs::read_to_string("/proc/uptime")
+        .ok()
+        .and_then(|content| content.split_whitespace().next().and_then(|v| v.parse::<f64>().ok()));
+    for boot in history.iter_mut().filter(|b| b.shutdown == ShutdownKind::Running) {
+        boot.duration_seconds = uptime.map(|u| u as u64);
+    }
+    history
+}
+
+// journalctl --list-boots kaydı; index 0 çalışan açılıştır
+struct JournalBoot {
+    index: i64,
+    boot_id: String,
+    first_entry: DateTime<Local>,
+    last_entry: Option<DateTime<Local>>,
+}
+
+// Açılış kayıtları ve okundukları kaynak
+fn journal_boots() -> Option<(Vec<BootRecord>, &'static str)> {
+    let (boots, source) = match list_boots(&["-o", "json"]).and_then(|out| parse_list_boots_json(&out)) {
+        Some(boots) => (boots, "journalctl --list-boots -o json"),
+        // systemd 251 öncesinde JSON çıktısı yoktur, düz metin okunur
+        None => (
+            parse_list_boots_text(&String::from_utf8_lossy(&list_boots(&["--utc"])?)),
+            "journalctl --list-boots",
+        ),
+    };
+    if boots.is_empty() {
+        return None;
+    }
+
+    let clean = clean_shutdown_boots();
+    let records = boots
+        .into_iter()
+        .map(|boot| {
+            let running = boot.index == 0;
+            let ended = boot.last_entry.filter(|_| !running);
+            BootRecord {
+                shutdown: classify_shutdown(&boot, &clean, has_kernel_crash),
+                started_at: boot.first_entry.to_rfc3339(),
+                ended_at: ended.map(|e| e.to_rfc3339()),
+                duration_seconds: ended.map(|e| (e - boot.first_entry).num_seconds().max(0) as u64),
+                boot_id: Some(boot.boot_id),
+                started: boot.first_entry,
+            }
+        })
+        .collect();
+    Some((records, source))
+}
+
+fn list_boots(args: &[&str]) -> Option<Vec<u8>> {
+    Command::new("journalctl")
+        .args(["--list-boots", "--no-pager", "-q"])
+        .args(args)
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| o.stdout)
+}
+
+// systemd 251+: [{"index":-1,"boot_id":"...","first_entry":usec,"last_entry":usec}]
+fn parse_list_boots_json(content: &[u8]) -> Option<Vec<JournalBoot>> {
+    let boots: Vec<Value> = serde_json::from_slice(content).ok()?;
+    let micros = |boot: &Value, key: &str| {
+        boot.get(key)
+            .and_then(Value::as_i64)
+            .and_then(DateTime::from_timestamp_micros)
+            .map(|dt| dt.with_timezone(&Local))
+    };
+    Some(
+        boots
+            .iter()
+            .filter_map(|boot| {
+                Some(JournalBoot {
+                    index: boot.get("index").and_then(Value::as_i64)?,
+                    boot_id: boot.get("boot_id").and_then(Value::as_str)?.to_string(),
+                    first_entry: micros(boot, "first_entry")?,
+                    last_entry: micros(boot, "last_entry"),
+                })
+            })
+            .collect(),
+    )
+}
+
+// --utc ile düz metin; eski sürümler zamanları "—" ile ayırır, yeniler başlık satırı ekler:
+// " -1 9f2c...1e Thu 2023-10-19 07:00:01 UTC—Thu 2023-10-19 12:00:00 UTC"
+fn parse_list_boots_text(content: &str) -> Vec<JournalBoot> {
+    static LINE: OnceLock<Regex> = OnceLock::new();
+    static TIMESTAMP: OnceLock<Regex> = OnceLock::new();
+    let line_re = LINE.get_or_init(|| Regex::new(r"^\s*(-?\d+)\s+([0-9a-f]{32})\s").unwrap());
+    let timestamp = TIMESTAMP.get_or_init(|| Regex::new(r"(\d{4}-\d{2}-\d{2} \d{2}:\d{2}:\d{2}) UTC").unwrap());
+    let parse = |text: &str| {
+        NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S")
+            .ok()
+            .map(|naive| Utc.from_utc_datetime(&naive).with_timezone(&Local))
+    };
+
+    content
+        .lines()
+        .filter_map(|line| {
+            let caps = line_re.captures(line)?;
+            let mut times = timestamp.captures_iter(&line[caps.get(0)?.end()..]);
+            Some(JournalBoot {
+                index: caps[1].parse().ok()?,
+                boot_id: caps[2].to_string(),
+                first_entry: parse(&times.next()?[1])?,
+                last_entry: times.next().and_then(|t| parse(&t[1])),
+            })
+        })
+        .collect()
+}
+
+// Kapanış mesajı yoksa açılış beklenmedik şekilde bitmiştir; çekirdek hatası varsa çökme, yoksa güç kaybı
+fn classify_shutdown(boot: &JournalBoot, clean: &BTreeSet<String>, crashed: impl Fn(&str) -> bool) -> ShutdownKind {
+    if boot.index == 0 {
+        ShutdownKind::Running
+    } else if clean.contains(&boot.boot_id) {
+        ShutdownKind::Clean
+    } else if crashed(&boot.boot_id) {
+        ShutdownKind::Crash
+    } else {
+        ShutdownKind::PowerLoss
+    }
+}
+
+// Kapanış mesajı bulunan açılışlar; tek indeksli alan sorgusu, günlüklerin tamamı okunmaz
+fn clean_shutdown_boots() -> BTreeSet<String> {
+    Command::new("journalctl")
+        .args(["-q", "--no-pager", "-o", "json", "--output-fields=_BOOT_ID"])
+        .arg(format!("MESSAGE_ID={}", SHUTDOWN_MESSAGE_ID))
+        .output()
+        .map(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+                .filter_map(|entry| entry.get("_BOOT_ID").and_then(Value::as_str).map(String::from))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// Temiz kapanmayan açılışta kritik çekirdek mesajı (panic, oops, kilitlenme) varsa çökme sayılır
+fn has_kernel_crash(boot_id: &str) -> bool {
+    Command::new("journalctl")
+        .args(["-q", "--no-pager", "-k", "-p", "0..2", "-n", "1", "-o", "cat", "-b", boot_id])
+        .output()
+        .map(|output| output.status.success() && !output.stdout.iter().all(u8::is_ascii_whitespace))
+        .unwrap_or(false)
+}
+
+// journal yoksa wtmp: "reboot   system boot  5.15.0 Thu Oct 19 10:00:00 2023 - Thu Oct 19 12:00:00 2023  (02:00)"
+fn wtmp_boots() -> Vec<BootRecord> {
+    let Ok(output) = Command::new("last").args(["-F", "-x", "reboot"]).output() else {
+        return Vec::new();
+    };
+    parse_last_reboot(&String::from_utf8_lossy(&output.stdout))
+}
+
+fn parse_last_reboot(content: &str) -> Vec<BootRecord> {
+    static TIMESTAMP: OnceLock<Regex> = OnceLock::new();
+    let timestamp = TIMESTAMP.get_or_init(|| Regex::new(r"\w{3} \w{3} +\d+ \d{2}:\d{2}:\d{2} \d{4}").unwrap());
+    let parse = |text: &str| {
+        NaiveDateTime::parse_from_str(&text.split_whitespace().collect::<Vec<_>>().join(" "), "%a %b %d %H:%M:%S %Y")
+            .ok()
+            .and_then(|naive| Local.from_local_datetime(&naive).earliest())
+    };
+
+    content
+        .lines()
+        .filter(|line| line.starts_with("reboot"))
+        .filter_map(|line| {
+            let mut times = timestamp.find_iter(line);
+            let start = times.next()?;
+            let started = parse(start.as_str())?;
+            let ended = times.next().and_then(|end| parse(end.as_str()));
+            let rest = &line[start.end()..];
+
+            let shutdown = if rest.contains("still running") {
+                ShutdownKind::Running
+            } else if rest.contains("crash") {
+                ShutdownKind::Unclean
+            } else if ended.is_some() || rest.contains("down") {
+                ShutdownKind::Clean
+            } else {
+                ShutdownKind::Unknown
+            };
+
+            Some(BootRecord {
+                boot_id: None,
+                started_at: started.to_rfc3339(),
+                ended_at: ended.map(|e| e.to_rfc3339()),
+                duration_seconds: ended.map(|e| (e - started).num_seconds().max(0) as u64),
+                shutdown,
+                started,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compares_rhel_z_stream_kernels() {
+        assert_eq!(compare_versions("4.18.0-513.24.1.el8_9.x86_64", "4.18.0-513.el8.x86_64"), Ordering::Greater);
+        assert_eq!(compare_versions("4.18.0-513.el8.x86_64", "4.18.0-513.24.1.el8_9.x86_64"), Ordering::Less);
+        assert_eq!(compare_versions("4.18.0-553.5.1.el8_10.x86_64", "4.18.0-513.24.1.el8_9.x86_64"), Ordering::Greater);
+        assert_eq!(compare_versions("5.14.0-362.24.1.el9_3.x86_64", "5.14.0-362.8.1.el9_3.x86_64"), Ordering::Greater);
+        assert_eq!(compare_versions("5.14.0-427.13.1.el9_4.x86_64", "5.14.0-362.24.1.el9_3.x86_64"), Ordering::Greater);
+        assert_eq!(compare_versions("5.14.0-362.el9.x86_64", "5.14.0-362.8.1.el9_3.x86_64"), Ordering::Less);
+        assert_eq!(compare_versions("5.14.0-362.8.1.el9_3.x86_64", "5.14.0-362.8.1.el9_3.x86_64"), Ordering::Equal);
+    }
+
+    #[test]
+    fn compares_debian_abi_and_suffixes() {
+        assert_eq!(compare_versions("5.15.0-91-generic", "5.15.0-9-generic"), Ordering::Greater);
+        assert_eq!(compare_versions("6.1.0-17-amd64", "6.1.0-13-amd64"), Ordering::Greater);
+        assert_eq!(compare_versions("6.1.0-9-amd64", "6.1.0-13-amd64"), Ordering::Less);
+        assert_eq!(compare_versions("4.19.0-26+deb10u1", "4.19.0-26"), Ordering::Greater);
+        assert_eq!(compare_versions("4.19.0-26+deb10u2", "4.19.0-26+deb10u1"), Ordering::Greater);
+        assert_eq!(compare_versions("6.1.0-18+deb12u1", "6.1.0-19"), Ordering::Less);
+        assert_eq!(compare_versions("6.1.0-010", "6.1.0-9"), Ordering::Greater);
+    }
+
+    #[test]
+    fn compares_tilde_and_caret() {
+        assert_eq!(compare_versions("6.8.0~rc1", "6.8.0"), Ordering::Less);
+        assert_eq!(compare_versions("6.8.0~rc2", "6.8.0~rc1"), Ordering::Greater);
+        assert_eq!(compare_versions("1.0^git1", "1.0"), Ordering::Greater);
+        assert_eq!(compare_versions("1.0^git1", "1.0.1"), Ordering::Less);
+    }
+
+    #[test]
+    fn finds_deleted_shared_libraries_in_maps() {
+        let libraries = deleted_libraries(include_str!("../tests/fixtures/reboot/maps.txt"));
+        assert_eq!(
+            libraries.into_iter().collect::<Vec<_>>(),
+            vec!["/usr/lib/x86_64-linux-gnu/libcrypto.so.3", "/usr/lib/x86_64-linux-gnu/libssl.so.3"]
+        );
+    }
+
+    fn local(text: &str) -> DateTime<Local> {
+        let naive = NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S").unwrap();
+        Local.from_local_datetime(&naive).earliest().unwrap()
+    }
+
+    fn utc(text: &str) -> DateTime<Local> {
+        let naive = NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S").unwrap();
+        Utc.from_utc_datetime(&naive).with_timezone(&Local)
+    }
+
+    #[test]
+    fn parses_last_reboot_records() {
+        let boots = parse_last_reboot(include_str!("../tests/fixtures/reboot/last_x.txt"));
+        let kinds: Vec<ShutdownKind> = boots.iter().map(|b| b.shutdown).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ShutdownKind::Running,
+                ShutdownKind::Clean,
+                ShutdownKind::Unclean,
+                ShutdownKind::Clean,
+                ShutdownKind::Clean
+            ]
+        );
+
+        assert_eq!(boots[0].started, local("2023-10-19 10:00:04"));
+        assert_eq!(boots[0].ended_at, None);
+
+        assert_eq!(boots[1].started, local("2023-10-16 08:12:40"));
+        assert_eq!(boots[1].ended_at, Some(local("2023-10-19 09:58:12").to_rfc3339()));
+        assert_eq!(boots[1].duration_seconds, Some(265_532));
+
+        // Kapanış kaydı olmayan açılış
+        assert_eq!(boots[2].started, local("2023-10-15 22:01:05"));
+        assert_eq!(boots[2].duration_seconds, None);
+
+        // "down": kapanış zamanı bilinmeyen temiz kapanış; tek haneli gün iki boşlukla yazılır
+        assert_eq!(boots[3].ended_at, None);
+        assert_eq!(boots[4].started, local("2023-10-06 12:00:00"));
+    }
+
+    #[test]
+    fn parses_journal_boot_lists() {
+        let json = parse_list_boots_json(include_bytes!("../tests/fixtures/reboot/list_boots.json")).unwrap();
+        let text = parse_list_boots_text(include_str!("../tests/fixtures/reboot/list_boots.txt"));
+        let legacy = parse_list_boots_text(include_str!("../tests/fixtures/reboot/list_boots_legacy.txt"));
+
+        for boots in [&json, &text, &legacy] {
+            assert_eq!(boots.len(), 3);
+            assert_eq!(boots[0].index, -2);
+            assert_eq!(boots[0].boot_id, "4b1a0d1ce8d3425bbf7e2b1a5d9a8c77");
+            assert_eq!(boots[0].first_entry, utc("2023-10-15 19:01:05"));
+            assert_eq!(boots[0].last_entry, Some(utc("2023-10-16 05:10:02")));
+            assert_eq!(boots[2].index, 0);
+            assert_eq!(boots[2].boot_id, "e3c55b0f6d8a4f2d9d5e1c7b2a4f6e80");
+        }
+    }
+
+    #[test]
+    fn classifies_journal_shutdowns() {
+        let boots = parse_list_boots_text(include_str!("../tests/fixtures/reboot/list_boots.txt"));
+        let clean: BTreeSet<String> = ["9f2c7d3e5b6a4c1d8e0f2a3b4c5d6e7f".to_string()].into();
+        let crashed = |id: &str| id == "4b1a0d1ce8d3425bbf7e2b1a5d9a8c77";
+
+        assert_eq!(classify_shutdown(&boots[0], &clean, crashed), ShutdownKind::Crash);
+        assert_eq!(classify_shutdown(&boots[1], &clean, crashed), ShutdownKind::Clean);
+        assert_eq!(classify_shutdown(&boots[2], &clean, crashed), ShutdownKind::Running);
+        assert_eq!(classify_shutdown(&boots[0], &clean, |_| false), ShutdownKind::PowerLoss);
+    }
+}
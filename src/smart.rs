@@ -0,0 +1,190 @@
+use crate::config::SmartConfig;
+use crate::hardware::BlockDevice;
+use crate::virt;
+use serde::Serialize;
+use serde_json::Value;
+use std::process::Command;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// SMART sorguları diskleri uyandırabilir ve yavaştır; sonuçlar aralık boyunca tekrar kullanılır
+static LAST_RESULT: Mutex<Option<(Instant, Vec<DiskHealth>)>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DiskHealth {
+    pub device: String,
+    pub model: Option<String>,
+    pub serial: Option<String>,
+    pub protocol: Option<String>,
+    pub passed: Option<bool>,
+    pub temperature_celsius: Option<i64>,
+    pub reallocated_sectors: Option<u64>,
+    pub pending_sectors: Option<u64>,
+    pub media_errors: Option<u64>,
+    pub percentage_used: Option<u64>,
+    pub power_on_hours: Option<u64>,
+    pub source: String,
+}
+
+pub fn get_disk_health(devices: &[BlockDevice], config: &SmartConfig) -> Vec<DiskHealth> {
+    // Konteynerde diskler ana makineye aittir
+    if !config.enabled || virt::in_container() {
+        return Vec::new();
+    }
+
+    let mut last = LAST_RESULT.lock().unwrap_or_else(|e| e.into_inner());
+    let interval = Duration::from_secs(config.interval_minutes * 60);
+    if let Some((at, result)) = last.as_ref() {
+        if at.elapsed() < interval {
+            return result.clone();
+        }
+    }
+
+    // Sanal diskler (virtio) SMART desteklemez, dm/md aygıtlarının taşıyıcısı yoktur
+    let result: Vec<DiskHealth> = devices
+        .iter()
+        .filter(|d| matches!(d.transport.as_deref(), Some("sata") | Some("scsi") | Some("nvme") | Some("usb")))
+        .filter_map(|d| query_device(&d.name))
+        .collect();
+    *last = Some((Instant::now(), result.clone()));
+    result
+}
+
+fn query_device(name: &str) -> Option<DiskHealth> {
+    let path = format!("/dev/{}", name);
+
+    // smartctl çıkış kodu bit maskesidir; disk hatalı olsa da JSON yazılır
+    if let Ok(output) = Command::new("smartctl").args(["--json", "-a", &path]).output() {
+        if let Some(health) = serde_json::from_slice(&output.stdout)
+            .ok()
+            .and_then(|json| parse_smartctl(&path, &json))
+        {
+            return Some(health);
+        }
+    }
+
+    if name.starts_with("nvme") {
+        let output = Command::new("nvme")
+            .args(["smart-log", "--output-format=json", &path])
+            .output()
+            .ok()?;
+        let json: Value = serde_json::from_slice(&output.stdout).ok()?;
+        return Some(parse_nvme_smart_log(&path, &json));
+    }
+    None
+}
+
+fn parse_smartctl(device: &str, json: &Value) -> Option<DiskHealth> {
+    // Aygıt açılamadıysa device bölümü de olmaz
+    json.get("device")?;
+
+    let attribute = |id: u64| -> Option<u64> {
+        json.pointer("/ata_smart_attributes/table")?
+            .as_array()?
+            .iter()
+            .find(|attr| attr.get("id").and_then(Value::as_u64) == Some(id))?
+            .pointer("/raw/value")?
+            .as_u64()
+    };
+    let nvme_log = |key: &str| -> Option<u64> {
+        json.get("nvme_smart_health_information_log")?.get(key)?.as_u64()
+    };
+
+    Some(DiskHealth {
+        device: device.to_string(),
+        model: json.get("model_name").and_then(Value::as_str).map(String::from),
+        serial: json.get("serial_number").and_then(Value::as_str).map(String::from),
+        protocol: json.pointer("/device/protocol").and_then(Value::as_str).map(String::from),
+        passed: json.pointer("/smart_status/passed").and_then(Value::as_bool),
+        temperature_celsius: json.pointer("/temperature/current").and_then(Value::as_i64),
+        // 5: Reallocated_Sector_Ct, 197: Current_Pending_Sector; SAS disklerde büyüyen kusur listesi
+        reallocated_sectors: attribute(5).or_else(|| json.get("scsi_grown_defect_list").and_then(Value::as_u64)),
+        pending_sectors: attribute(197),
+        media_errors: nvme_log("media_errors"),
+        percentage_used: nvme_log("percentage_used"),
+        power_on_hours: json.pointer("/power_on_time/hours").and_then(Value::as_u64),
+        source: "smartctl".to_string(),
+    })
+}
+
+// nvme-cli çıktısı: sıcaklık Kelvin cinsindendir, alan adları smartctl'den farklıdır
+fn parse_nvme_smart_log(device: &str, json: &Value) -> DiskHealth {
+    let field = |key: &str| json.get(key).and_then(Value::as_u64);
+    DiskHealth {
+        device: device.to_string(),
+        model: None,
+        serial: None,
+        protocol: Some("NVMe".to_string()),
+        passed: field("critical_warning").map(|warning| warning == 0),
+        temperature_celsius: field("temperature").map(|kelvin| kelvin as i64 - 273),
+        reallocated_sectors: None,
+        pending_sectors: None,
+        media_errors: field("media_errors"),
+        percentage_used: field("percent_used").or_else(|| field("percentage_used")),
+        power_on_hours: field("power_on_hours"),
+        source: "nvme-cli".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(content: &str) -> Value {
+        serde_json::from_str(content).unwrap()
+    }
+
+    #[test]
+    fn parses_healthy_ata_disk() {
+        let json = fixture(include_str!("../tests/fixtures/smart/smartctl_ata.json"));
+        let health = parse_smartctl("/dev/sda", &json).unwrap();
+        assert_eq!(health.model.as_deref(), Some("Samsung SSD 860 EVO 500GB"));
+        assert_eq!(health.serial.as_deref(), Some("S3Z2NB0K123456A"));
+        assert_eq!(health.protocol.as_deref(), Some("ATA"));
+        assert_eq!(health.passed, Some(true));
+        assert_eq!(health.temperature_celsius, Some(33));
+        assert_eq!(health.reallocated_sectors, Some(0));
+        assert_eq!(health.pending_sectors, Some(0));
+        assert_eq!(health.power_on_hours, Some(21834));
+        assert_eq!(health.percentage_used, None);
+    }
+
+    #[test]
+    fn parses_failing_ata_disk() {
+        let json = fixture(include_str!("../tests/fixtures/smart/smartctl_ata_failing.json"));
+        let health = parse_smartctl("/dev/sdb", &json).unwrap();
+        assert_eq!(health.passed, Some(false));
+        assert_eq!(health.reallocated_sectors, Some(1984));
+        assert_eq!(health.pending_sectors, Some(16));
+        assert_eq!(health.power_on_hours, Some(52117));
+    }
+
+    #[test]
+    fn parses_smartctl_nvme_disk() {
+        let json = fixture(include_str!("../tests/fixtures/smart/smartctl_nvme.json"));
+        let health = parse_smartctl("/dev/nvme0n1", &json).unwrap();
+        assert_eq!(health.protocol.as_deref(), Some("NVMe"));
+        assert_eq!(health.passed, Some(true));
+        assert_eq!(health.temperature_celsius, Some(38));
+        assert_eq!(health.percentage_used, Some(7));
+        assert_eq!(health.media_errors, Some(0));
+        assert_eq!(health.power_on_hours, Some(8760));
+        assert_eq!(health.reallocated_sectors, None);
+    }
+
+    #[test]
+    fn parses_nvme_cli_smart_log() {
+        let json = fixture(include_str!("../tests/fixtures/smart/nvme_smart_log.json"));
+        let health = parse_nvme_smart_log("/dev/nvme1n1", &json);
+        assert_eq!(health.passed, Some(true));
+        assert_eq!(health.temperature_celsius, Some(39));
+        assert_eq!(health.percentage_used, Some(12));
+        assert_eq!(health.power_on_hours, Some(14320));
+    }
+
+    #[test]
+    fn rejects_smartctl_error_output() {
+        let json = fixture(r#"{"smartctl":{"exit_status":2,"messages":[{"string":"Smartctl open device: /dev/sdz failed: No such device","severity":"error"}]}}"#);
+        assert!(parse_smartctl("/dev/sdz", &json).is_none());
+    }
+}
@@ -0,0 +1,363 @@
+use crate::config::SshConfig;
+use crate::{glob_match, Finding, Severity};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// sshd yapılandırması nadiren değişir; sonuç aralık boyunca tekrar kullanılır
+static LAST_RESULT: Mutex<Option<(Instant, Option<SshInfo>)>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SshInfo {
+    pub source: String,
+    pub permit_root_login: String,
+    pub password_authentication: String,
+    pub ports: Vec<u16>,
+    pub ciphers: Vec<String>,
+    pub macs: Vec<String>,
+    pub kex_algorithms: Vec<String>,
+    pub allow_users: Vec<String>,
+    pub score: u8,
+    pub findings: Vec<Finding>,
+}
+
+type SshdSettings = HashMap<String, Vec<String>>;
+
+pub fn get_ssh_info(config: &SshConfig) -> Option<SshInfo> {
+    if !config.enabled {
+        return None;
+    }
+
+    let mut last = LAST_RESULT.lock().unwrap_or_else(|e| e.into_inner());
+    let interval = Duration::from_secs(config.interval_minutes * 60);
+    if let Some((at, result)) = last.as_ref() {
+        if at.elapsed() < interval {
+            return result.clone();
+        }
+    }
+
+    let result = read_ssh_info(config);
+    *last = Some((Instant::now(), result.clone()));
+    result
+}
+
+fn read_ssh_info(config: &SshConfig) -> Option<SshInfo> {
+    // Önce sshd -T ile etkin yapılandırmayı dene, olmazsa dosyayı ayrıştır
+    let effective = if config.use_sshd_t { read_sshd_effective() } else { None };
+    let (source, settings) = match effective {
+        Some(settings) => ("sshd -T".to_string(), settings),
+        None => {
+            let path = Path::new(&config.config_path);
+            if !path.exists() {
+                return None;
+            }
+            let mut settings = SshdSettings::new();
+            parse_sshd_config_file(path, &mut settings, 0);
+            (config.config_path.clone(), settings)
+        }
+    };
+    Some(assess(source, &settings, config))
+}
+
+fn assess(source: String, settings: &SshdSettings, config: &SshConfig) -> SshInfo {
+    let permit_root_login = first_value(settings, "permitrootlogin")
+        .unwrap_or("prohibit-password")
+        .to_lowercase();
+    let password_authentication = first_value(settings, "passwordauthentication")
+        .unwrap_or("yes")
+        .to_lowercase();
+
+    let mut ports: Vec<u16> = settings
+        .get("port")
+        .map(|values| values.iter().filter_map(|p| p.parse().ok()).collect())
+        .unwrap_or_default();
+    if ports.is_empty() {
+        ports.push(22);
+    }
+
+    let ciphers = algorithm_list(settings, "ciphers");
+    let macs = algorithm_list(settings, "macs");
+    let kex_algorithms = algorithm_list(settings, "kexalgorithms");
+    let allow_users: Vec<String> = settings
+        .get("allowusers")
+        .map(|values| values.iter().flat_map(|v| v.split_whitespace()).map(String::from).collect())
+        .unwrap_or_default();
+    let allow_groups = settings.contains_key("allowgroups");
+
+    let mut findings = Vec::new();
+
+    if !config.allowed_root_login.iter().any(|v| v.eq_ignore_ascii_case(&permit_root_login)) {
+        findings.push(Finding {
+            check: "ssh.permit_root_login".to_string(),
+            severity: if permit_root_login == "yes" { Severity::Critical } else { Severity::Warning },
+            message: format!("PermitRootLogin {} olarak ayarlı", permit_root_login),
+            expected: Some(config.allowed_root_login.join("|")),
+            actual: Some(permit_root_login.clone()),
+        });
+    }
+
+    if password_authentication == "yes" && !config.allow_password_authentication {
+        findings.push(Finding {
+            check: "ssh.password_authentication".to_string(),
+            severity: Severity::Warning,
+            message: "Parola ile kimlik doğrulama açık".to_string(),
+            expected: Some("no".to_string()),
+            actual: Some(password_authentication.clone()),
+        });
+    }
+
+    for (check, name, offered, weak) in [
+        ("ssh.ciphers", "cipher", &ciphers, &config.weak_ciphers),
+        ("ssh.macs", "MAC", &macs, &config.weak_macs),
+        ("ssh.kex_algorithms", "KEX", &kex_algorithms, &config.weak_kex),
+    ] {
+        let found: Vec<&str> = offered
+            .iter()
+            .filter(|alg| weak.iter().any(|w| w.eq_ignore_ascii_case(alg)))
+            .map(String::as_str)
+            .collect();
+        if !found.is_empty() {
+            findings.push(Finding {
+                check: check.to_string(),
+                severity: Severity::Warning,
+                message: format!("Zayıf {} algoritmaları etkin: {}", name, found.join(", ")),
+                expected: None,
+                actual: Some(found.join(",")),
+            });
+        }
+    }
+
+    if config.require_allow_users && allow_users.is_empty() && !allow_groups {
+        findings.push(Finding {
+            check: "ssh.allow_users".to_string(),
+            severity: Severity::Warning,
+            message: "AllowUsers/AllowGroups tanımlı değil, tüm kullanıcılar giriş yapabilir".to_string(),
+            expected: Some("AllowUsers".to_string()),
+            actual: None,
+        });
+    }
+
+    if !config.allowed_ports.is_empty() {
+        let unexpected: Vec<String> = ports
+            .iter()
+            .filter(|p| !config.allowed_ports.contains(p))
+            .map(|p| p.to_string())
+            .collect();
+        if !unexpected.is_empty() {
+            findings.push(Finding {
+                check: "ssh.port".to_string(),
+                severity: Severity::Info,
+                message: format!("SSH beklenmeyen portlarda dinliyor: {}", unexpected.join(", ")),
+                expected: Some(
+                    config.allowed_ports.iter().map(|p| p.to_string()).collect::<Vec<_>>().join(","),
+                ),
+                actual: Some(unexpected.join(",")),
+            });
+        }
+    }
+
+    let penalty: u32 = findings
+        .iter()
+        .map(|f| match f.severity {
+            Severity::Critical => 40,
+            Severity::Warning => 15,
+            Severity::Info => 5,
+        })
+        .sum();
+    let score = 100u32.saturating_sub(penalty) as u8;
+
+    SshInfo {
+        source,
+        permit_root_login,
+        password_authentication,
+        ports,
+        ciphers,
+        macs,
+        kex_algorithms,
+        allow_users,
+        score,
+        findings,
+    }
+}
+
+fn read_sshd_effective() -> Option<SshdSettings> {
+    // sshd PATH'te olmayabilir (nobody kullanıcısı), tam yolu da dene
+    for binary in ["sshd", "/usr/sbin/sshd"] {
+        if let Ok(output) = Command::new(binary).arg("-T").output() {
+            if output.status.success() && !output.stdout.is_empty() {
+                let mut settings = SshdSettings::new();
+                for line in String::from_utf8_lossy(&output.stdout).lines() {
+                    if let Some((key, value)) = parse_sshd_line(line) {
+                        settings.entry(key).or_default().push(value);
+                    }
+                }
+                return Some(settings);
+            }
+        }
+    }
+    None
+}
+
+// Match görüldüyse true döner; Match bloğu dosya sonuna kadar sürer, dahil eden dosyadaki
+// sonraki satırlar da artık genel bölümde değildir
+fn parse_sshd_config_file(path: &Path, settings: &mut SshdSettings, depth: usize) -> bool {
+    if depth > 8 {
+        return false;
+    }
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(_) => return false,
+    };
+
+    for line in content.lines() {
+        let Some((key, value)) = parse_sshd_line(line) else {
+            continue;
+        };
+        match key.as_str() {
+            // Match blokları genel ayarları etkilemez, sadece genel bölümü oku
+            "match" => return true,
+            "include" => {
+                for pattern in value.split_whitespace() {
+                    for included in expand_include(pattern) {
+                        if parse_sshd_config_file(&included, settings, depth + 1) {
+                            return true;
+                        }
+                    }
+                }
+            }
+            _ => settings.entry(key).or_default().push(value),
+        }
+    }
+    false
+}
+
+fn parse_sshd_line(line: &str) -> Option<(String, String)> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+    let (key, value) = line.split_once(|c: char| c.is_whitespace() || c == '=')?;
+    let value = value.trim_start_matches(|c: char| c.is_whitespace() || c == '=').trim();
+    Some((key.to_lowercase(), value.trim_matches('"').to_string()))
+}
+
+fn expand_include(pattern: &str) -> Vec<PathBuf> {
+    let path = if pattern.starts_with('/') {
+        PathBuf::from(pattern)
+    } else {
+        Path::new("/etc/ssh").join(pattern)
+    };
+
+    let file_pattern = path.file_name().map(|f| f.to_string_lossy().into_owned()).unwrap_or_default();
+    if !file_pattern.contains('*') && !file_pattern.contains('?') {
+        return vec![path];
+    }
+
+    let dir = path.parent().unwrap_or(Path::new("/etc/ssh"));
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .filter(|e| glob_match(&file_pattern, &e.file_name().to_string_lossy()))
+                .map(|e| e.path())
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+fn first_value<'a>(settings: &'a SshdSettings, key: &str) -> Option<&'a str> {
+    settings.get(key).and_then(|values| values.first()).map(String::as_str)
+}
+
+fn algorithm_list(settings: &SshdSettings, key: &str) -> Vec<String> {
+    match first_value(settings, key) {
+        // "-" ile başlayan liste varsayılanlardan çıkarma yapar, eklenen algoritma yok
+        Some(value) if value.starts_with('-') => Vec::new(),
+        Some(value) => value
+            .trim_start_matches(['+', '^'])
+            .split(',')
+            .map(|s| s.trim().to_lowercase())
+            .filter(|s| !s.is_empty())
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // İlk dosya ana yapılandırmadır; "{dir}" geçici dizinle değiştirilir
+    fn parse(test: &str, files: &[(&str, &str)]) -> SshdSettings {
+        let dir = std::env::temp_dir().join(format!("staffmon-ssh-{}-{}", test, std::process::id()));
+        fs::create_dir_all(dir.join("sshd_config.d")).unwrap();
+        for (name, content) in files {
+            fs::write(dir.join(name), content.replace("{dir}", &dir.to_string_lossy())).unwrap();
+        }
+        let mut settings = SshdSettings::new();
+        parse_sshd_config_file(&dir.join(files[0].0), &mut settings, 0);
+        fs::remove_dir_all(&dir).unwrap();
+        settings
+    }
+
+    #[test]
+    fn includes_are_read_in_place_and_first_value_wins() {
+        let settings = parse("includes", &[
+            (
+                "sshd_config",
+                "Include {dir}/sshd_config.d/*.conf\nPermitRootLogin yes\nPort 22\nPasswordAuthentication yes\n",
+            ),
+            ("sshd_config.d/50-cloud.conf", "PasswordAuthentication no\n"),
+            ("sshd_config.d/10-hardening.conf", "PermitRootLogin no\nPort 2222\n"),
+        ]);
+        // Drop-in dosyaları ada göre sıralanır ve Include satırının yerinde okunur
+        assert_eq!(first_value(&settings, "permitrootlogin"), Some("no"));
+        assert_eq!(first_value(&settings, "passwordauthentication"), Some("no"));
+        assert_eq!(settings["port"], vec!["2222", "22"]);
+    }
+
+    #[test]
+    fn match_in_included_file_ends_global_section() {
+        let settings = parse("match-include", &[
+            (
+                "sshd_config",
+                "Include {dir}/sshd_config.d/*.conf\nPermitRootLogin yes\n",
+            ),
+            ("sshd_config.d/10-sftp.conf", "X11Forwarding no\nMatch User sftp\n  PasswordAuthentication yes\n"),
+        ]);
+        assert_eq!(first_value(&settings, "x11forwarding"), Some("no"));
+        assert_eq!(first_value(&settings, "passwordauthentication"), None);
+        assert_eq!(first_value(&settings, "permitrootlogin"), None);
+    }
+
+    #[test]
+    fn match_block_in_main_file() {
+        let settings = parse("match-main", &[(
+            "sshd_config",
+            "PermitRootLogin=no\nMatch Address 10.0.0.0/8\n  PermitRootLogin yes\n",
+        )]);
+        assert_eq!(first_value(&settings, "permitrootlogin"), Some("no"));
+    }
+
+    #[test]
+    fn parses_algorithm_lists() {
+        let mut settings = SshdSettings::new();
+        settings.insert("ciphers".to_string(), vec!["aes256-gcm@openssh.com,AES128-CTR".to_string()]);
+        settings.insert("macs".to_string(), vec!["+hmac-sha1".to_string()]);
+        settings.insert("kexalgorithms".to_string(), vec!["-diffie-hellman-group1-sha1".to_string()]);
+        assert_eq!(algorithm_list(&settings, "ciphers"), vec!["aes256-gcm@openssh.com", "aes128-ctr"]);
+        assert_eq!(algorithm_list(&settings, "macs"), vec!["hmac-sha1"]);
+        assert!(algorithm_list(&settings, "kexalgorithms").is_empty());
+        assert!(algorithm_list(&settings, "hostkeyalgorithms").is_empty());
+
+        let info = assess("test".to_string(), &settings, &SshConfig::default());
+        assert!(info.findings.iter().any(|f| f.check == "ssh.macs"));
+        assert!(!info.findings.iter().any(|f| f.check == "ssh.ciphers"));
+    }
+}
@@ -0,0 +1,319 @@
+use crate::config::StorageConfig;
+use crate::virt;
+use serde::Serialize;
+use serde_json::Value;
+use std::fs;
+use std::process::Command;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+type CommandReports = (Vec<VolumeGroup>, Vec<ThinPool>, Vec<ZfsPool>);
+
+// lvs/vgs/zpool her çağrıda disk taraması yapabilir; sonuç aralık boyunca tekrar kullanılır
+static LAST_REPORTS: Mutex<Option<(Instant, CommandReports)>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MdArray {
+    pub name: String,
+    pub level: Option<String>,
+    pub state: String,
+    pub devices: Vec<String>,
+    pub failed_devices: Vec<String>,
+    pub total_devices: Option<u32>,
+    pub active_devices: Option<u32>,
+    pub degraded: bool,
+    // resync, recovery, check, reshape
+    pub sync_action: Option<String>,
+    pub sync_progress_percent: Option<f32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct VolumeGroup {
+    pub name: String,
+    pub size_bytes: u64,
+    pub free_bytes: u64,
+    pub pv_count: u32,
+    pub lv_count: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ThinPool {
+    pub name: String,
+    pub vg_name: String,
+    pub size_bytes: u64,
+    pub data_percent: Option<f32>,
+    pub metadata_percent: Option<f32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ZfsPool {
+    pub name: String,
+    pub health: String,
+    pub size_bytes: u64,
+    pub allocated_bytes: u64,
+    pub free_bytes: u64,
+    pub capacity_percent: Option<u32>,
+    pub fragmentation_percent: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StorageHealth {
+    pub md_arrays: Vec<MdArray>,
+    pub volume_groups: Vec<VolumeGroup>,
+    pub thin_pools: Vec<ThinPool>,
+    pub zfs_pools: Vec<ZfsPool>,
+}
+
+pub fn get_storage_health(config: &StorageConfig) -> Option<StorageHealth> {
+    if !config.enabled || virt::in_container() {
+        return None;
+    }
+
+    let (volume_groups, thin_pools, zfs_pools) = command_reports(config);
+    Some(StorageHealth {
+        md_arrays: fs::read_to_string("/proc/mdstat")
+            .map(|content| parse_mdstat(&content))
+            .unwrap_or_default(),
+        volume_groups,
+        thin_pools,
+        zfs_pools,
+    })
+}
+
+fn command_reports(config: &StorageConfig) -> CommandReports {
+    let mut last = LAST_REPORTS.lock().unwrap_or_else(|e| e.into_inner());
+    let interval = Duration::from_secs(config.interval_minutes * 60);
+    if let Some((at, reports)) = last.as_ref() {
+        if at.elapsed() < interval {
+            return reports.clone();
+        }
+    }
+
+    let (volume_groups, thin_pools) = get_lvm();
+    let reports = (volume_groups, thin_pools, get_zfs_pools());
+    *last = Some((Instant::now(), reports.clone()));
+    reports
+}
+
+// md0 : active raid1 sdb1[1] sda1[0](F)
+//       976630464 blocks super 1.2 [2/1] [U_]
+//       [==>..................]  recovery = 12.6% (123/976630464) finish=80.1min speed=170000K/sec
+fn parse_mdstat(content: &str) -> Vec<MdArray> {
+    let mut arrays: Vec<MdArray> = Vec::new();
+
+    for line in content.lines() {
+        if let Some((name, rest)) = line.split_once(" : ").filter(|(name, _)| name.starts_with("md")) {
+            let mut fields = rest.split_whitespace().peekable();
+            let state = fields.next().unwrap_or("").to_string();
+            // "active (auto-read-only) raid1" gibi ek durum bilgileri atlanır
+            while fields.peek().map(|f| f.starts_with('(')).unwrap_or(false) {
+                fields.next();
+            }
+            let level = fields.peek().filter(|f| !f.contains('[')).map(|f| f.to_string());
+            if level.is_some() {
+                fields.next();
+            }
+
+            let mut devices = Vec::new();
+            let mut failed_devices = Vec::new();
+            for device in fields {
+                let Some(name) = device.split('[').next() else {
+                    continue;
+                };
+                if device.ends_with("(F)") {
+                    failed_devices.push(name.to_string());
+                }
+                devices.push(name.to_string());
+            }
+
+            arrays.push(MdArray {
+                name: name.trim().to_string(),
+                level,
+                // inactive: dizi birleştirilemedi (eksik üye), veri erişilemez durumda
+                degraded: !failed_devices.is_empty() || state == "inactive",
+                state,
+                devices,
+                failed_devices,
+                total_devices: None,
+                active_devices: None,
+                sync_action: None,
+                sync_progress_percent: None,
+            });
+            continue;
+        }
+
+        let Some(array) = arrays.last_mut() else {
+            continue;
+        };
+        let line = line.trim();
+
+        // [2/1] [U_]: toplam/çalışan aygıt sayısı
+        if let Some(counts) = line
+            .split_whitespace()
+            .find(|f| f.starts_with('[') && f.ends_with(']') && f.contains('/'))
+        {
+            let counts = counts.trim_matches(['[', ']']);
+            if let Some((total, active)) = counts.split_once('/') {
+                array.total_devices = total.parse().ok();
+                array.active_devices = active.parse().ok();
+                if array.active_devices < array.total_devices {
+                    array.degraded = true;
+                }
+            }
+        }
+
+        for action in ["resync", "recovery", "check", "reshape", "repair"] {
+            // "recovery = 12.6%" veya "resync=DELAYED"
+            let Some(rest) = line.split_once(action).map(|(_, rest)| rest.trim_start()) else {
+                continue;
+            };
+            let Some(value) = rest.strip_prefix('=') else {
+                continue;
+            };
+            array.sync_action = Some(action.to_string());
+            array.sync_progress_percent = value
+                .split_whitespace()
+                .next()
+                .and_then(|v| v.strip_suffix('%'))
+                .and_then(|v| v.parse().ok());
+            break;
+        }
+    }
+    arrays
+}
+
+fn get_lvm() -> (Vec<VolumeGroup>, Vec<ThinPool>) {
+    let volume_groups = lvm_report(
+        "vgs",
+        &["-o", "vg_name,vg_size,vg_free,pv_count,lv_count"],
+        "vg",
+    )
+    .iter()
+    .map(|vg| VolumeGroup {
+        name: report_str(vg, "vg_name"),
+        size_bytes: report_num(vg, "vg_size").unwrap_or(0),
+        free_bytes: report_num(vg, "vg_free").unwrap_or(0),
+        pv_count: report_num(vg, "pv_count").unwrap_or(0) as u32,
+        lv_count: report_num(vg, "lv_count").unwrap_or(0) as u32,
+    })
+    .collect();
+
+    let thin_pools = lvm_report(
+        "lvs",
+        &["-o", "lv_name,vg_name,lv_size,data_percent,metadata_percent,segtype"],
+        "lv",
+    )
+    .iter()
+    .filter(|lv| report_str(lv, "segtype") == "thin-pool")
+    .map(|lv| ThinPool {
+        name: report_str(lv, "lv_name"),
+        vg_name: report_str(lv, "vg_name"),
+        size_bytes: report_num(lv, "lv_size").unwrap_or(0),
+        data_percent: report_str(lv, "data_percent").parse().ok(),
+        metadata_percent: report_str(lv, "metadata_percent").parse().ok(),
+    })
+    .collect();
+
+    (volume_groups, thin_pools)
+}
+
+// {"report": [{"vg": [{"vg_name": "vg0", "vg_size": "1000"}]}]}; tüm değerler dize olarak gelir
+fn lvm_report(command: &str, fields: &[&str], key: &str) -> Vec<Value> {
+    Command::new(command)
+        .args(["--reportformat", "json", "--units", "b", "--nosuffix"])
+        .args(fields)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| serde_json::from_slice::<Value>(&output.stdout).ok())
+        .and_then(|json| json.pointer(&format!("/report/0/{}", key)).and_then(Value::as_array).cloned())
+        .unwrap_or_default()
+}
+
+fn report_str(row: &Value, key: &str) -> String {
+    row.get(key).and_then(Value::as_str).unwrap_or("").trim().to_string()
+}
+
+fn report_num(row: &Value, key: &str) -> Option<u64> {
+    report_str(row, key).parse().ok()
+}
+
+fn get_zfs_pools() -> Vec<ZfsPool> {
+    // -H: başlıksız, sekmeyle ayrılmış; -p: tam sayı değerler
+    let Ok(output) = Command::new("zpool")
+        .args(["list", "-H", "-p", "-o", "name,health,size,alloc,free,cap,frag"])
+        .output()
+    else {
+        return Vec::new();
+    };
+    if !output.status.success() {
+        return Vec::new();
+    }
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split('\t').collect();
+            if fields.len() < 7 {
+                return None;
+            }
+            let percent = |v: &str| v.trim_end_matches('%').parse().ok();
+            Some(ZfsPool {
+                name: fields[0].to_string(),
+                health: fields[1].to_string(),
+                size_bytes: fields[2].parse().unwrap_or(0),
+                allocated_bytes: fields[3].parse().unwrap_or(0),
+                free_bytes: fields[4].parse().unwrap_or(0),
+                capacity_percent: percent(fields[5]),
+                fragmentation_percent: percent(fields[6]),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_mdstat_arrays() {
+        let arrays = parse_mdstat(include_str!("../tests/fixtures/storage/mdstat.txt"));
+        assert_eq!(arrays.len(), 3);
+
+        assert_eq!(arrays[0].name, "md0");
+        assert_eq!(arrays[0].level.as_deref(), Some("raid1"));
+        assert_eq!(arrays[0].devices, vec!["sdb1", "sda1"]);
+        assert!(!arrays[0].degraded);
+        assert_eq!(arrays[0].sync_action, None);
+
+        assert_eq!(arrays[1].name, "md1");
+        assert_eq!(arrays[1].level.as_deref(), Some("raid5"));
+        assert_eq!(arrays[1].failed_devices, vec!["sdd1"]);
+        assert_eq!(arrays[1].total_devices, Some(3));
+        assert_eq!(arrays[1].active_devices, Some(2));
+        assert!(arrays[1].degraded);
+        assert_eq!(arrays[1].sync_action.as_deref(), Some("recovery"));
+        assert_eq!(arrays[1].sync_progress_percent, Some(12.6));
+
+        assert_eq!(arrays[2].name, "md2");
+        assert_eq!(arrays[2].sync_action.as_deref(), Some("resync"));
+        assert_eq!(arrays[2].sync_progress_percent, None);
+        assert!(!arrays[2].degraded);
+    }
+
+    #[test]
+    fn treats_inactive_arrays_as_degraded() {
+        let arrays = parse_mdstat(include_str!("../tests/fixtures/storage/mdstat_inactive.txt"));
+        assert_eq!(arrays.len(), 2);
+
+        assert_eq!(arrays[0].name, "md127");
+        assert_eq!(arrays[0].state, "inactive");
+        assert_eq!(arrays[0].level, None);
+        assert_eq!(arrays[0].devices, vec!["sdc1", "sdb1"]);
+        assert_eq!(arrays[0].total_devices, None);
+        assert!(arrays[0].degraded);
+
+        assert_eq!(arrays[1].name, "md0");
+        assert!(!arrays[1].degraded);
+    }
+}
@@ -0,0 +1,296 @@
+use crate::ServiceInfo;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use zbus::blocking::Connection;
+use zbus::zvariant::{OwnedObjectPath, OwnedValue, Value};
+
+const DESTINATION: &str = "org.freedesktop.systemd1";
+const MANAGER_PATH: &str = "/org/freedesktop/systemd1";
+const MANAGER_INTERFACE: &str = "org.freedesktop.systemd1.Manager";
+const PROPERTIES_INTERFACE: &str = "org.freedesktop.DBus.Properties";
+
+// Servis başına NRestarts örnekleri; flapping tespiti için döngüler arası tutulur
+static RESTART_HISTORY: Mutex<RestartHistory> = Mutex::new(BTreeMap::new());
+
+type RestartHistory = BTreeMap<String, VecDeque<(Instant, u32)>>;
+
+// Sistem veriyolu bağlantısı döngüler arasında paylaşılır; hata alınırsa bir sonraki döngüde yeniden kurulur
+static CONNECTION: Mutex<Option<Connection>> = Mutex::new(None);
+
+// Etkinleştirme durumları nadiren değişir; ListUnitFiles liste yenileme aralığında bir çağrılır
+static UNIT_FILE_STATES: Mutex<Option<(Instant, HashMap<String, String>)>> = Mutex::new(None);
+
+// Birim adı -> son okunan özellikler; durum değişmedikçe GetAll liste yenileme aralığında bir yapılır
+static UNIT_PROPERTIES: Mutex<BTreeMap<String, CachedProperties>> = Mutex::new(BTreeMap::new());
+
+struct CachedProperties {
+    // (ActiveState, SubState)
+    state: (String, String),
+    fetched_at: Instant,
+    properties: HashMap<String, OwnedValue>,
+}
+
+// ListUnits dönüş tipi: ad, açıklama, load, active, sub, following, yol, iş kimliği, iş tipi, iş yolu
+type UnitStatus = (String, String, String, String, String, String, OwnedObjectPath, u32, String, OwnedObjectPath);
+
+// Seçilen servisleri systemd'den paylaşılan D-Bus bağlantısı üzerinden alır, süreç başlatmaz.
+// Her döngüde yalnızca ListUnitsByPatterns çağrılır; birim özellikleri durum değişince yeniden okunur
+pub fn list_services(selected: impl Fn(&str) -> bool) -> zbus::Result<Vec<ServiceInfo>> {
+    let conn = connection()?;
+    let result = query_services(&conn, selected);
+    if result.is_err() {
+        *CONNECTION.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+    result
+}
+
+fn connection() -> zbus::Result<Connection> {
+    let mut cached = CONNECTION.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(conn) = cached.as_ref() {
+        return Ok(conn.clone());
+    }
+    let conn = Connection::system()?;
+    *cached = Some(conn.clone());
+    Ok(conn)
+}
+
+fn query_services(conn: &Connection, selected: impl Fn(&str) -> bool) -> zbus::Result<Vec<ServiceInfo>> {
+    let units: Vec<UnitStatus> = conn
+        .call_method(
+            Some(DESTINATION),
+            MANAGER_PATH,
+            Some(MANAGER_INTERFACE),
+            "ListUnitsByPatterns",
+            &(vec!["loaded"], vec!["*.service"]),
+        )?
+        .body()?;
+
+    let unit_file_states = unit_file_states(conn)?;
+    let mut cache = UNIT_PROPERTIES.lock().unwrap_or_else(|e| e.into_inner());
+
+    let mut services = Vec::new();
+    for (name, _, _, active_state, sub_state, _, unit_path, _, _, _) in units {
+        if name.contains('@') || !selected(&name) {
+            continue;
+        }
+
+        let state = (active_state.clone(), sub_state.clone());
+        let stale = cache
+            .get(&name)
+            .map(|c| c.state != state || c.fetched_at.elapsed() >= crate::LIST_REFRESH_INTERVAL)
+            .unwrap_or(true);
+        if stale {
+            let properties = get_service_properties(conn, &unit_path).unwrap_or_default();
+            cache.insert(
+                name.clone(),
+                CachedProperties {
+                    state,
+                    fetched_at: Instant::now(),
+                    properties,
+                },
+            );
+        }
+        let properties = &cache[&name].properties;
+
+        let unit_file_state = unit_file_states.get(&name).cloned();
+        // StateChangeTimestamp: epoch'tan beri mikrosaniye, 0 = hiç değişmedi
+        let state_changed_at = property_u64(properties, "StateChangeTimestamp")
+            .filter(|ts| *ts != 0)
+            .and_then(|ts| chrono::DateTime::from_timestamp_micros(ts as i64))
+            .map(|dt| dt.with_timezone(&chrono::Local).to_rfc3339());
+
+        services.push(ServiceInfo {
+            active: active_state == "active",
+            enabled: matches!(unit_file_state.as_deref(), Some("enabled") | Some("enabled-runtime")),
+            version: None,
+            exec_path: exec_start_path(properties),
+            critical: false,
+            active_state: Some(active_state),
+            sub_state: Some(sub_state),
+            unit_file_state,
+            main_pid: property_u32(properties, "MainPID").filter(|pid| *pid != 0),
+            // Bellek muhasebesi kapalıysa systemd u64::MAX döner
+            memory_bytes: property_u64(properties, "MemoryCurrent").filter(|m| *m != u64::MAX),
+            restarts: property_u32(properties, "NRestarts"),
+            state_changed_at,
+            restarts_in_window: None,
+            flapping: false,
+            // Birim çalışmıyorsa ControlGroup boş döner
+            control_group: property_string(properties, "ControlGroup").filter(|cg| !cg.is_empty()),
+            resources: None,
+            name,
+        });
+    }
+
+    // Kaldırılan veya artık seçilmeyen birimlerin özellikleri bellekte tutulmaz
+    cache.retain(|name, _| services.iter().any(|s| &s.name == name));
+    Ok(services)
+}
+
+fn unit_file_states(conn: &Connection) -> zbus::Result<HashMap<String, String>> {
+    let mut cached = UNIT_FILE_STATES.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some((at, states)) = cached.as_ref() {
+        if at.elapsed() < crate::LIST_REFRESH_INTERVAL {
+            return Ok(states.clone());
+        }
+    }
+
+    let unit_files: Vec<(String, String)> = conn
+        .call_method(Some(DESTINATION), MANAGER_PATH, Some(MANAGER_INTERFACE), "ListUnitFiles", &())?
+        .body()?;
+    let states: HashMap<String, String> = unit_files
+        .into_iter()
+        .filter_map(|(path, state)| {
+            Path::new(&path)
+                .file_name()
+                .map(|name| (name.to_string_lossy().into_owned(), state))
+        })
+        .collect();
+    *cached = Some((Instant::now(), states.clone()));
+    Ok(states)
+}
+
+// Boş arayüz adıyla GetAll, Unit ve Service arayüzlerinin özelliklerini tek çağrıda döner
+fn get_service_properties(conn: &Connection, unit_path: &OwnedObjectPath) -> zbus::Result<HashMap<String, OwnedValue>> {
+    conn.call_method(
+        Some(DESTINATION),
+        unit_path.as_str(),
+        Some(PROPERTIES_INTERFACE),
+        "GetAll",
+        &"",
+    )?
+    .body()
+}
+
+// Pencere içindeki yeniden başlatma sayısını hesaplar ve eşiği aşan servisleri işaretler
+pub fn detect_flapping(services: &mut [ServiceInfo], window: Duration, threshold: u32) {
+    let mut history = RESTART_HISTORY.lock().unwrap_or_else(|e| e.into_inner());
+    update_flapping(&mut history, services, window, threshold, Instant::now());
+}
+
+fn update_flapping(
+    history: &mut RestartHistory,
+    services: &mut [ServiceInfo],
+    window: Duration,
+    threshold: u32,
+    now: Instant,
+) {
+    for service in services.iter_mut() {
+        let Some(restarts) = service.restarts else {
+            continue;
+        };
+        let samples = history.entry(service.name.clone()).or_default();
+        // Servis durdurulup başlatılınca NRestarts sıfırlanır, geçmişi de sıfırla
+        if samples.back().map(|(_, last)| *last > restarts).unwrap_or(false) {
+            samples.clear();
+        }
+        samples.push_back((now, restarts));
+        while samples.front().map(|(t, _)| now.duration_since(*t) > window).unwrap_or(false) {
+            samples.pop_front();
+        }
+
+        let oldest = samples.front().map(|(_, r)| *r).unwrap_or(restarts);
+        let in_window = restarts - oldest;
+        service.restarts_in_window = Some(in_window);
+        service.flapping = in_window >= threshold;
+    }
+
+    history.retain(|name, _| services.iter().any(|s| &s.name == name));
+}
+
+// ExecStart: a(sasbttttuii), ilk eleman çalıştırılan ikilinin yolu
+fn exec_start_path(properties: &HashMap<String, OwnedValue>) -> Option<String> {
+    let value: &Value = properties.get("ExecStart")?;
+    let value = match value {
+        Value::Value(inner) => inner.as_ref(),
+        other => other,
+    };
+    let Value::Array(commands) = value else {
+        return None;
+    };
+    commands.get().iter().find_map(|command| match command {
+        Value::Structure(structure) => match structure.fields().first() {
+            Some(Value::Str(path)) => Some(path.as_str().to_string()),
+            _ => None,
+        },
+        _ => None,
+    })
+}
+
+fn property_u32(properties: &HashMap<String, OwnedValue>, key: &str) -> Option<u32> {
+    properties.get(key).and_then(|v| u32::try_from(v.clone()).ok())
+}
+
+fn property_u64(properties: &HashMap<String, OwnedValue>, key: &str) -> Option<u64> {
+    properties.get(key).and_then(|v| u64::try_from(v.clone()).ok())
+}
+
+fn property_string(properties: &HashMap<String, OwnedValue>, key: &str) -> Option<String> {
+    properties.get(key).and_then(|v| String::try_from(v.clone()).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn service(name: &str, restarts: u32) -> ServiceInfo {
+        ServiceInfo {
+            name: name.to_string(),
+            restarts: Some(restarts),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn flags_restarts_within_window() {
+        let mut history = RestartHistory::new();
+        let window = Duration::from_secs(600);
+        let start = Instant::now();
+
+        let mut services = vec![service("app.service", 2)];
+        update_flapping(&mut history, &mut services, window, 3, start);
+        assert_eq!(services[0].restarts_in_window, Some(0));
+        assert!(!services[0].flapping);
+
+        services[0].restarts = Some(4);
+        update_flapping(&mut history, &mut services, window, 3, start + Duration::from_secs(60));
+        assert_eq!(services[0].restarts_in_window, Some(2));
+        assert!(!services[0].flapping);
+
+        // Eşiğe ulaşınca flapping sayılır
+        services[0].restarts = Some(5);
+        update_flapping(&mut history, &mut services, window, 3, start + Duration::from_secs(120));
+        assert_eq!(services[0].restarts_in_window, Some(3));
+        assert!(services[0].flapping);
+
+        // Pencere dışına çıkan örnekler sayılmaz
+        services[0].restarts = Some(6);
+        update_flapping(&mut history, &mut services, window, 3, start + Duration::from_secs(700));
+        assert_eq!(services[0].restarts_in_window, Some(1));
+        assert!(!services[0].flapping);
+    }
+
+    #[test]
+    fn resets_when_restart_counter_drops() {
+        let mut history = RestartHistory::new();
+        let window = Duration::from_secs(600);
+        let start = Instant::now();
+
+        let mut services = vec![service("app.service", 10), service("db.service", 0)];
+        update_flapping(&mut history, &mut services, window, 3, start);
+
+        // systemctl restart NRestarts'ı sıfırlar; bu bir yeniden başlama döngüsü değildir
+        services[0].restarts = Some(0);
+        update_flapping(&mut history, &mut services, window, 3, start + Duration::from_secs(30));
+        assert_eq!(services[0].restarts_in_window, Some(0));
+        assert!(!services[0].flapping);
+
+        // Listeden çıkan servislerin geçmişi silinir
+        let mut services = vec![service("app.service", 0)];
+        update_flapping(&mut history, &mut services, window, 3, start + Duration::from_secs(60));
+        assert!(!history.contains_key("db.service"));
+        assert_eq!(services[0].restarts_in_window, Some(0));
+    }
+}
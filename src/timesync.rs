@@ -0,0 +1,224 @@
+use serde::Serialize;
+use std::fs;
+use std::process::Command;
+
+#[derive(Debug, Default, Serialize)]
+pub struct TimeSyncInfo {
+    // chrony, systemd-timesyncd, ntpd
+    pub daemon: Option<String>,
+    pub synchronized: Option<bool>,
+    pub offset_ms: Option<f64>,
+    pub stratum: Option<u32>,
+    pub source: Option<String>,
+}
+
+pub fn get_time_sync_info() -> TimeSyncInfo {
+    let mut info = chrony()
+        .or_else(timesyncd)
+        .or_else(ntpd)
+        .unwrap_or_default();
+
+    // Hiçbir istemci yanıt vermezse çekirdeğin senkron durumu timedatectl ile sorulur
+    if info.synchronized.is_none() {
+        info.synchronized = command_output("timedatectl", &["show", "-p", "NTPSynchronized", "--value"])
+            .map(|value| value.trim() == "yes");
+    }
+    info
+}
+
+// /etc/timezone (Debian) veya /etc/localtime bağlantısının hedefi
+pub fn get_timezone() -> Option<String> {
+    if let Ok(timezone) = fs::read_to_string("/etc/timezone") {
+        let timezone = timezone.trim();
+        if !timezone.is_empty() {
+            return Some(timezone.to_string());
+        }
+    }
+    let target = fs::read_link("/etc/localtime").ok()?;
+    let target = target.to_string_lossy();
+    target
+        .split_once("zoneinfo/")
+        .map(|(_, zone)| zone.to_string())
+}
+
+// chronyc -c tracking: refid,ad,stratum,reftime,sistem saati farkı (s),son fark,...,leap durumu
+fn chrony() -> Option<TimeSyncInfo> {
+    parse_chrony_tracking(&command_output("chronyc", &["-c", "tracking"])?)
+}
+
+fn parse_chrony_tracking(output: &str) -> Option<TimeSyncInfo> {
+    let fields: Vec<&str> = output.trim().split(',').collect();
+    if fields.len() < 14 {
+        return None;
+    }
+    let stratum = fields[2].parse().ok();
+    Some(TimeSyncInfo {
+        daemon: Some("chrony".to_string()),
+        synchronized: Some(fields[13] != "Not synchronised" && stratum.map(|s: u32| s > 0).unwrap_or(false)),
+        offset_ms: fields[4].parse::<f64>().ok().map(|s| s * 1000.0),
+        stratum,
+        source: Some(fields[1].to_string()).filter(|s| !s.is_empty()),
+    })
+}
+
+// timedatectl timesync-status: "Server: 185.125.190.57 (ntp.ubuntu.com)", "Offset: -1.234ms"
+fn timesyncd() -> Option<TimeSyncInfo> {
+    Some(parse_timesync_status(&command_output("timedatectl", &["timesync-status"])?))
+}
+
+fn parse_timesync_status(output: &str) -> TimeSyncInfo {
+    let field = |key: &str| {
+        output
+            .lines()
+            .filter_map(|line| line.trim().split_once(':'))
+            .find(|(k, _)| k.trim() == key)
+            .map(|(_, v)| v.trim().to_string())
+    };
+
+    let offset_ms = field("Offset").and_then(|offset| parse_duration_ms(&offset));
+    TimeSyncInfo {
+        daemon: Some("systemd-timesyncd".to_string()),
+        // Sunucudan yanıt alınmadıysa Offset satırı olmaz; Leap "normal" ise senkron kabul edilir
+        synchronized: field("Leap").map(|leap| leap == "normal" && offset_ms.is_some()),
+        offset_ms,
+        stratum: field("Stratum").and_then(|s| s.parse().ok()),
+        source: field("Server"),
+    }
+}
+
+// ntpq -pn: seçili eş '*' ile başlar; remote refid st t when poll reach delay offset jitter
+fn ntpd() -> Option<TimeSyncInfo> {
+    parse_ntpq_peers(&command_output("ntpq", &["-pn"])?)
+}
+
+fn parse_ntpq_peers(output: &str) -> Option<TimeSyncInfo> {
+    // ntpd çalışmıyorsa ntpq hata verip (çoğu sürümde sıfır çıkış koduyla) eş tablosu yazmaz;
+    // eşi olmayan bir ntpd ise "No association ID's returned" der
+    let has_table = output.lines().any(|line| line.split_whitespace().take(2).eq(["remote", "refid"]));
+    if !has_table && !output.contains("No association ID's returned") {
+        return None;
+    }
+
+    let selected = output
+        .lines()
+        .find(|line| line.starts_with('*'))
+        .map(|line| line.trim_start_matches('*').split_whitespace().collect::<Vec<_>>());
+
+    Some(match selected {
+        Some(fields) if fields.len() >= 10 => TimeSyncInfo {
+            daemon: Some("ntpd".to_string()),
+            synchronized: Some(true),
+            offset_ms: fields[8].parse().ok(),
+            // Yerel stratum, seçili eşin bir fazlasıdır
+            stratum: fields[2].parse::<u32>().ok().map(|s| s + 1),
+            source: Some(fields[0].to_string()),
+        },
+        _ => TimeSyncInfo {
+            daemon: Some("ntpd".to_string()),
+            synchronized: Some(false),
+            ..Default::default()
+        },
+    })
+}
+
+// "-1.234ms", "+512us", "1.5s", "2min 3.1s" -> milisaniye
+fn parse_duration_ms(value: &str) -> Option<f64> {
+    let sign = if value.trim_start().starts_with('-') { -1.0 } else { 1.0 };
+    let mut total = 0.0;
+    let mut parsed = false;
+    for part in value.split_whitespace() {
+        let part = part.trim_start_matches(['+', '-']);
+        let split = part.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+        let (number, unit) = part.split_at(split);
+        let number: f64 = number.parse().ok()?;
+        let factor = match unit {
+            "ns" => 0.000_001,
+            "us" | "µs" => 0.001,
+            "ms" => 1.0,
+            "s" => 1000.0,
+            "min" => 60_000.0,
+            _ => return None,
+        };
+        total += number * factor;
+        parsed = true;
+    }
+    parsed.then_some(sign * total)
+}
+
+fn command_output(cmd: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(cmd).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_chrony_tracking() {
+        let info = parse_chrony_tracking(include_str!("../tests/fixtures/timesync/chronyc_tracking.csv")).unwrap();
+        assert_eq!(info.daemon.as_deref(), Some("chrony"));
+        assert_eq!(info.synchronized, Some(true));
+        assert_eq!(info.stratum, Some(3));
+        assert_eq!(info.source.as_deref(), Some("162.159.200.123"));
+        assert!((info.offset_ms.unwrap() - -0.012345).abs() < 1e-9);
+
+        let unsynchronised = include_str!("../tests/fixtures/timesync/chronyc_tracking_unsync.csv");
+        let info = parse_chrony_tracking(unsynchronised).unwrap();
+        assert_eq!(info.synchronized, Some(false));
+        assert_eq!(info.stratum, Some(0));
+        assert_eq!(info.source, None);
+
+        assert!(parse_chrony_tracking("506 Cannot talk to daemon\n").is_none());
+    }
+
+    #[test]
+    fn parses_timesync_status() {
+        let info = parse_timesync_status(include_str!("../tests/fixtures/timesync/timesync_status.txt"));
+        assert_eq!(info.daemon.as_deref(), Some("systemd-timesyncd"));
+        assert_eq!(info.synchronized, Some(true));
+        assert_eq!(info.offset_ms, Some(-1.234));
+        assert_eq!(info.stratum, Some(2));
+        assert_eq!(info.source.as_deref(), Some("185.125.190.57 (ntp.ubuntu.com)"));
+
+        // Sunucudan henüz yanıt alınmadı; karar NTPSynchronized değerine bırakılır
+        let info = parse_timesync_status(include_str!("../tests/fixtures/timesync/timesync_status_unsync.txt"));
+        assert_eq!(info.synchronized, None);
+        assert_eq!(info.offset_ms, None);
+        assert_eq!(info.stratum, None);
+    }
+
+    #[test]
+    fn parses_ntpq_peers() {
+        let info = parse_ntpq_peers(include_str!("../tests/fixtures/timesync/ntpq_pn.txt")).unwrap();
+        assert_eq!(info.daemon.as_deref(), Some("ntpd"));
+        assert_eq!(info.synchronized, Some(true));
+        assert_eq!(info.offset_ms, Some(-0.518));
+        assert_eq!(info.stratum, Some(4));
+        assert_eq!(info.source.as_deref(), Some("162.159.200.1"));
+
+        let info = parse_ntpq_peers(include_str!("../tests/fixtures/timesync/ntpq_pn_unsync.txt")).unwrap();
+        assert_eq!(info.synchronized, Some(false));
+        let info = parse_ntpq_peers("No association ID's returned\n").unwrap();
+        assert_eq!(info.synchronized, Some(false));
+
+        // ntpq kurulu ama ntpd çalışmıyor: senkron değil, daemon yok sayılır
+        assert!(parse_ntpq_peers("").is_none());
+        assert!(parse_ntpq_peers("localhost: timed out, nothing received\n***Request timed out\n").is_none());
+    }
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration_ms("-1.234ms"), Some(-1.234));
+        assert_eq!(parse_duration_ms("+512us"), Some(0.512));
+        assert_eq!(parse_duration_ms("1.5s"), Some(1500.0));
+        assert_eq!(parse_duration_ms("2min 3.1s"), Some(123_100.0));
+        assert_eq!(parse_duration_ms("-2min 3s"), Some(-123_000.0));
+        assert_eq!(parse_duration_ms("250ns"), Some(0.00025));
+        assert_eq!(parse_duration_ms("5 parsecs"), None);
+        assert_eq!(parse_duration_ms(""), None);
+    }
+}
@@ -0,0 +1,176 @@
+use crate::config::ServicesConfig;
+use crate::packages::PackageManager;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+// İkili dosyanın değiştirilme zamanı ve bulunan sürüm
+type CacheEntry = (Option<SystemTime>, Option<String>);
+
+// İkili dosya yolu -> (mtime, sürüm); dosya güncellenmedikçe tekrar sorgulanmaz
+static VERSION_CACHE: Mutex<BTreeMap<String, CacheEntry>> = Mutex::new(BTreeMap::new());
+
+const SEARCH_PATH: &[&str] = &["/usr/local/sbin", "/usr/local/bin", "/usr/sbin", "/usr/bin", "/sbin", "/bin"];
+
+pub fn get_service_version(
+    service: &str,
+    exec_path: Option<&str>,
+    pkg_manager: PackageManager,
+    config: &ServicesConfig,
+) -> Option<String> {
+    // Yapılandırmada açıkça tanımlanmış sürüm komutu varsa onu kullan
+    let short_name = service.trim_end_matches(".service");
+    if let Some(command) = config
+        .version_commands
+        .get(service)
+        .or_else(|| config.version_commands.get(short_name))
+    {
+        let (program, args) = command.split_first()?;
+        let key = format!("cmd:{}", command.join(" "));
+        return cached(&key, resolve_binary(program).as_deref(), || run_version_command(program, args));
+    }
+
+    // Aksi halde ExecStart ikilisinin ait olduğu paketin sürümü
+    let binary = resolve_binary(exec_path?)?;
+    let key = binary.to_string_lossy().into_owned();
+    cached(&key, Some(&binary), || package_version(&binary, pkg_manager))
+}
+
+fn cached(key: &str, binary: Option<&Path>, lookup: impl FnOnce() -> Option<String>) -> Option<String> {
+    let mtime = binary.and_then(|b| fs::metadata(b).and_then(|m| m.modified()).ok());
+
+    let mut cache = VERSION_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some((cached_mtime, version)) = cache.get(key) {
+        if *cached_mtime == mtime {
+            return version.clone();
+        }
+    }
+
+    let version = lookup();
+    cache.insert(key.to_string(), (mtime, version.clone()));
+    version
+}
+
+fn resolve_binary(program: &str) -> Option<PathBuf> {
+    if program.contains('/') {
+        let path = Path::new(program);
+        return path.exists().then(|| path.to_path_buf());
+    }
+    SEARCH_PATH
+        .iter()
+        .map(|dir| Path::new(dir).join(program))
+        .find(|path| path.exists())
+}
+
+fn run_version_command(program: &str, args: &[String]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    // Bazı programlar (nginx -v gibi) sürümü stderr'e yazar
+    first_line(&[&String::from_utf8_lossy(&output.stdout), &String::from_utf8_lossy(&output.stderr)])
+}
+
+fn first_line(outputs: &[&str]) -> Option<String> {
+    outputs
+        .iter()
+        .flat_map(|out| out.lines())
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(String::from)
+}
+
+fn package_version(binary: &Path, pkg_manager: PackageManager) -> Option<String> {
+    // usrmerge sistemlerde paket veritabanı /bin yolunu, systemd /usr/bin yolunu gösterebilir
+    let mut candidates = vec![binary.to_path_buf()];
+    if let Ok(canonical) = fs::canonicalize(binary) {
+        if canonical != binary {
+            candidates.push(canonical);
+        }
+    }
+    if let Ok(stripped) = binary.strip_prefix("/usr") {
+        candidates.push(Path::new("/").join(stripped));
+    }
+
+    candidates.iter().find_map(|path| owning_package_version(path, pkg_manager))
+}
+
+fn owning_package_version(path: &Path, pkg_manager: PackageManager) -> Option<String> {
+    let path = path.to_string_lossy();
+    match pkg_manager {
+        PackageManager::Apt => {
+            let package = parse_dpkg_owner(&command_output("dpkg", &["-S", &path])?)?;
+            command_output("dpkg-query", &["-W", "-f=${Version}", &package])
+        }
+        PackageManager::Yum | PackageManager::Dnf | PackageManager::Zypper => {
+            command_output("rpm", &["-qf", "--qf", "%{VERSION}-%{RELEASE}\\n", &path])
+                .and_then(|out| out.lines().next().map(String::from))
+        }
+        PackageManager::Pacman => {
+            command_output("pacman", &["-Qo", &path]).and_then(|out| parse_pacman_owner(&out))
+        }
+        PackageManager::Unknown => None,
+    }
+}
+
+// "nginx-core: /usr/sbin/nginx", "libc6:amd64: /lib/x86_64-linux-gnu/libc.so.6" veya
+// "diversion by dash from: /bin/sh" satırları; birden çok sahip varsa ilki alınır
+fn parse_dpkg_owner(output: &str) -> Option<String> {
+    output
+        .lines()
+        .filter(|line| !line.starts_with("diversion by "))
+        .find_map(|line| line.split_once(": "))
+        .and_then(|(owners, _)| owners.split(',').next())
+        .map(|owner| owner.trim().split(':').next().unwrap_or(owner).to_string())
+        .filter(|owner| !owner.is_empty())
+}
+
+// "/usr/bin/nginx is owned by nginx 1.24.0-1"
+fn parse_pacman_owner(output: &str) -> Option<String> {
+    output
+        .lines()
+        .find(|line| line.contains(" is owned by "))
+        .and_then(|line| line.split_whitespace().last())
+        .map(String::from)
+}
+
+fn command_output(cmd: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(cmd).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!stdout.is_empty()).then_some(stdout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_dpkg_owner() {
+        let output = include_str!("../tests/fixtures/version/dpkg_search.txt");
+        let mut blocks = output.split("\n\n");
+        assert_eq!(parse_dpkg_owner(blocks.next().unwrap()).as_deref(), Some("nginx-core"));
+        assert_eq!(parse_dpkg_owner(blocks.next().unwrap()).as_deref(), Some("libc6"));
+        // Yönlendirme satırları atlanır, asıl sahip alınır
+        assert_eq!(parse_dpkg_owner(blocks.next().unwrap()).as_deref(), Some("dash"));
+        assert_eq!(parse_dpkg_owner(""), None);
+    }
+
+    #[test]
+    fn parses_pacman_owner() {
+        let output = include_str!("../tests/fixtures/version/pacman_qo.txt");
+        assert_eq!(parse_pacman_owner(output).as_deref(), Some("1.24.0-1"));
+        assert_eq!(parse_pacman_owner("error: No package owns /usr/bin/foo\n"), None);
+    }
+
+    #[test]
+    fn takes_first_non_empty_line() {
+        // nginx -v sürümü stderr'e yazar
+        let stderr = include_str!("../tests/fixtures/version/nginx_v.txt");
+        assert_eq!(first_line(&["", stderr]).as_deref(), Some("nginx version: nginx/1.24.0"));
+        assert_eq!(first_line(&["\n  OpenSSH_9.6p1\n", stderr]).as_deref(), Some("OpenSSH_9.6p1"));
+        assert_eq!(first_line(&["", "\n"]), None);
+    }
+}
@@ -0,0 +1,162 @@
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+const DMI_ROOT: &str = "/sys/class/dmi/id";
+
+// Çalışma ortamı süreç boyunca değişmez, bir kez tespit edilir
+static DETECTED: OnceLock<VirtualizationInfo> = OnceLock::new();
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct VirtualizationInfo {
+    // kvm, vmware, hyperv, xen, virtualbox, wsl, ...
+    pub hypervisor: Option<String>,
+    // docker, podman, lxc, kubernetes, ...
+    pub container: Option<String>,
+    pub is_virtual: bool,
+}
+
+pub fn detect() -> &'static VirtualizationInfo {
+    DETECTED.get_or_init(|| {
+        let root = Path::new("/");
+        let container = detect_container(root);
+        let hypervisor = detect_hypervisor(root);
+        VirtualizationInfo {
+            is_virtual: container.is_some() || hypervisor.is_some(),
+            hypervisor,
+            container,
+        }
+    })
+}
+
+// Çekirdek, donanım ve depolama ana makineye aittir; konteyner içinde bu kontroller atlanır.
+// systemd birimleri ise konteynerin kendi init sistemine ait olduğundan okunmaya devam eder.
+pub fn in_container() -> bool {
+    detect().container.is_some()
+}
+
+pub fn dmi_field(name: &str) -> Option<String> {
+    read_field(&Path::new(DMI_ROOT).join(name))
+}
+
+fn read_field(path: &Path) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+// Dosyalar root altında aranır; testlerde örnek bir kök dizin verilir
+fn detect_container(root: &Path) -> Option<String> {
+    if root.join(".dockerenv").exists() {
+        return Some("docker".to_string());
+    }
+    if root.join("run/.containerenv").exists() {
+        return Some("podman".to_string());
+    }
+
+    // systemd ve çoğu konteyner çalıştırıcısı PID 1'e container= değişkenini verir (root gerekir)
+    if let Ok(environ) = fs::read(root.join("proc/1/environ")) {
+        if let Some(value) = environ
+            .split(|b| *b == 0)
+            .filter_map(|entry| entry.strip_prefix(b"container="))
+            .map(|value| String::from_utf8_lossy(value).into_owned())
+            .find(|value| !value.is_empty())
+        {
+            return Some(value);
+        }
+    }
+
+    let cgroup = fs::read_to_string(root.join("proc/1/cgroup")).unwrap_or_default();
+    for (marker, kind) in [("kubepods", "kubernetes"), ("docker", "docker"), ("libpod", "podman"), ("lxc", "lxc")] {
+        if cgroup.contains(marker) {
+            return Some(kind.to_string());
+        }
+    }
+    None
+}
+
+fn detect_hypervisor(root: &Path) -> Option<String> {
+    // WSL2 Hyper-V üzerinde gerçek bir çekirdek çalıştırır ama DMI bilgisi sunmaz;
+    // Microsoft çekirdeği ile anlaşılır ("5.15.153.1-microsoft-standard-WSL2")
+    let osrelease = fs::read_to_string(root.join("proc/sys/kernel/osrelease")).unwrap_or_default();
+    if osrelease.to_lowercase().contains("microsoft") {
+        return Some("wsl".to_string());
+    }
+
+    let dmi_root = root.join(DMI_ROOT.trim_start_matches('/'));
+    let vendors: Vec<String> = ["sys_vendor", "product_name", "bios_vendor", "board_vendor"]
+        .iter()
+        .filter_map(|field| read_field(&dmi_root.join(field)))
+        .collect();
+    let known = [
+        ("KVM", "kvm"),
+        ("QEMU", "kvm"),
+        ("Amazon EC2", "kvm"),
+        ("Google", "kvm"),
+        ("VMware", "vmware"),
+        ("innotek", "virtualbox"),
+        ("VirtualBox", "virtualbox"),
+        ("Xen", "xen"),
+        ("Parallels", "parallels"),
+        ("Bochs", "bochs"),
+    ];
+    for vendor in &vendors {
+        if let Some((_, kind)) = known.iter().find(|(marker, _)| vendor.contains(marker)) {
+            return Some(kind.to_string());
+        }
+    }
+    // Hyper-V: "Microsoft Corporation" / "Virtual Machine"
+    if vendors.iter().any(|v| v.contains("Microsoft Corporation")) && vendors.iter().any(|v| v.contains("Virtual Machine")) {
+        return Some("hyperv".to_string());
+    }
+
+    // DMI olmayan Xen PV konukları
+    if let Ok(kind) = fs::read_to_string(root.join("sys/hypervisor/type")) {
+        let kind = kind.trim();
+        if !kind.is_empty() {
+            return Some(kind.to_string());
+        }
+    }
+
+    // CPUID hypervisor biti: türü bilinmeyen bir sanal makine
+    let cpuinfo = fs::read_to_string(root.join("proc/cpuinfo")).unwrap_or_default();
+    let has_flag = cpuinfo
+        .lines()
+        .find(|line| line.starts_with("flags"))
+        .map(|line| line.split_whitespace().any(|flag| flag == "hypervisor"))
+        .unwrap_or(false);
+    has_flag.then(|| "unknown".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn fixture(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/virt").join(name)
+    }
+
+    #[test]
+    fn detects_containers() {
+        assert_eq!(detect_container(&fixture("docker")).as_deref(), Some("docker"));
+        assert_eq!(detect_container(&fixture("lxc")).as_deref(), Some("lxc"));
+        assert_eq!(detect_container(&fixture("kubernetes")).as_deref(), Some("kubernetes"));
+        assert_eq!(detect_container(&fixture("kvm")), None);
+        assert_eq!(detect_container(&fixture("wsl2")), None);
+        assert_eq!(detect_container(&fixture("baremetal")), None);
+    }
+
+    #[test]
+    fn detects_hypervisors() {
+        assert_eq!(detect_hypervisor(&fixture("kvm")).as_deref(), Some("kvm"));
+        assert_eq!(detect_hypervisor(&fixture("hyperv")).as_deref(), Some("hyperv"));
+        // WSL2 konteyner değil, Hyper-V üzerindeki bir sanal makinedir
+        assert_eq!(detect_hypervisor(&fixture("wsl2")).as_deref(), Some("wsl"));
+        assert_eq!(detect_hypervisor(&fixture("xen-pv")).as_deref(), Some("xen"));
+        assert_eq!(detect_hypervisor(&fixture("unknown")).as_deref(), Some("unknown"));
+        assert_eq!(detect_hypervisor(&fixture("baremetal")), None);
+    }
+}